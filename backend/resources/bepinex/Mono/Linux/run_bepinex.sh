@@ -0,0 +1,20 @@
+#!/bin/sh
+# {{ MOD_FILES_PATH }} will be used by Rai Pal. This token gets replaced by the path where BepInEx is installed.
+# Starts the game with BepInEx loaded through Unity Doorstop, which gets injected with LD_PRELOAD.
+# Steam runs this script when the game's launch options are set to: "/path/to/run_bepinex.sh" %command%
+
+# Set to FALSE to start the game without BepInEx.
+export DOORSTOP_ENABLE=TRUE
+export DOORSTOP_INVOKE_DLL_PATH="{{MOD_FILES_PATH}}/BepInEx/core/BepInEx.Preloader.dll"
+export DOORSTOP_CORLIB_OVERRIDE_PATH=""
+
+export LD_LIBRARY_PATH="{{MOD_FILES_PATH}}/doorstop_libs:${LD_LIBRARY_PATH}"
+export LD_PRELOAD="{{DOORSTOP_LIBRARY}}:${LD_PRELOAD}"
+
+# Steam passes the full game command, otherwise we start the executable directly.
+if [ "$#" -eq 0 ]; then
+	cd "{{GAME_FOLDER_PATH}}" || exit 1
+	exec "{{GAME_EXECUTABLE_PATH}}"
+fi
+
+exec "$@"
@@ -15,6 +15,7 @@ use crate::{
 	mod_loaders::mod_loader,
 	owned_game,
 	remote_mod,
+	runnable_process::RunnableProcesses,
 	Error,
 	Result,
 };
@@ -25,6 +26,7 @@ pub struct AppState {
 	pub mod_loaders: Mutex<Option<mod_loader::Map>>,
 	pub local_mods: Mutex<Option<local_mod::Map>>,
 	pub remote_mods: Mutex<Option<remote_mod::Map>>,
+	pub runnable_processes: RunnableProcesses,
 }
 
 type TauriState<'a> = tauri::State<'a, AppState>;
@@ -0,0 +1,228 @@
+use std::{
+	collections::HashSet,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::warn;
+use reqwest::Url;
+
+use crate::{
+	paths,
+	serializable_struct,
+	signatures,
+	Error,
+	Result,
+};
+
+pub const DEFAULT_MOD_DATABASE_ID: &str = "rai-pal";
+const DEFAULT_MOD_DATABASE_URL: &str = "https://raicuparta.github.io/rai-pal-db";
+const DEFAULT_STEAM_IDS_URL: &str = "https://raicuparta.github.io/rai-pal-db/steam-ids";
+
+serializable_struct!(ModDatabaseSource {
+	// Shown on each remote mod, so we know which database it came from.
+	pub id: String,
+	// Can be https:// or file://. Each loader's database is read from `{url}/{loader_id}.json`.
+	pub url: String,
+	// Loaders this source has databases for. Empty means every loader.
+	#[serde(default)]
+	pub loader_ids: Vec<String>,
+	// Minisign public keys, as the base64 line of the .pub file. If there are any,
+	// the databases need a valid `.minisig` file next to them, and every download needs a signature.
+	#[serde(default)]
+	pub public_keys: Vec<String>,
+});
+
+serializable_struct!(DatabaseSources {
+	// When the same mod id shows up in multiple databases, the source listed first wins.
+	#[serde(default)]
+	pub mod_databases: Vec<ModDatabaseSource>,
+	// The official database always goes after the custom ones, unless it's excluded here.
+	#[serde(default)]
+	pub exclude_default_mod_database: bool,
+	// Replaces the official Steam ids lists.
+	pub steam_ids_url: Option<String>,
+	// Refuses to use any mod database source that doesn't have public keys.
+	#[serde(default)]
+	pub require_signatures: bool,
+});
+
+impl ModDatabaseSource {
+	pub fn has_loader(&self, loader_id: &str) -> bool {
+		self.loader_ids.is_empty() || self.loader_ids.iter().any(|id| id == loader_id)
+	}
+
+	// Sources are only verified when they have keys. That includes the official one,
+	// which gets whatever keys are bundled with the app.
+	pub const fn requires_signatures(&self) -> bool {
+		!self.public_keys.is_empty()
+	}
+}
+
+impl DatabaseSources {
+	pub const fn official_only() -> Self {
+		Self {
+			mod_databases: Vec::new(),
+			exclude_default_mod_database: false,
+			steam_ids_url: None,
+			require_signatures: false,
+		}
+	}
+
+	// Sources in order of precedence, including the official one.
+	// The official source is trusted with the keys bundled with the app.
+	pub fn get_mod_databases(&self, resources_path: &Path) -> Result<Vec<ModDatabaseSource>> {
+		let mut sources = self.mod_databases.clone();
+
+		if !self.exclude_default_mod_database {
+			let public_keys = signatures::read_bundled_keys(resources_path)?;
+			if public_keys.is_empty() {
+				warn!("No trusted keys bundled for the official mod database, so it won't be verified.");
+			}
+
+			sources.push(ModDatabaseSource {
+				id: DEFAULT_MOD_DATABASE_ID.to_string(),
+				url: DEFAULT_MOD_DATABASE_URL.to_string(),
+				loader_ids: Vec::new(),
+				public_keys,
+			});
+		}
+
+		if self.require_signatures {
+			if let Some(unsigned_source) =
+				sources.iter().find(|source| source.public_keys.is_empty())
+			{
+				return Err(Error::DatabaseSourceNotSigned(unsigned_source.id.clone()));
+			}
+		}
+
+		Ok(sources)
+	}
+
+	pub fn get_steam_ids_url(&self) -> &str {
+		self.steam_ids_url
+			.as_deref()
+			.unwrap_or(DEFAULT_STEAM_IDS_URL)
+	}
+
+	fn validate(&self) -> Result {
+		let mut source_ids = HashSet::new();
+
+		for source in &self.mod_databases {
+			if source.id == DEFAULT_MOD_DATABASE_ID {
+				return Err(Error::InvalidDatabaseSource(
+					source.id.clone(),
+					"source id can't be the same as the official database".to_string(),
+				));
+			}
+			if !is_valid_source_id(&source.id) {
+				return Err(Error::InvalidDatabaseSource(
+					source.id.clone(),
+					"source id can only have lowercase letters, numbers, `-` and `_`".to_string(),
+				));
+			}
+			if !source_ids.insert(source.id.as_str()) {
+				return Err(Error::InvalidDatabaseSource(
+					source.id.clone(),
+					"source id is used more than once".to_string(),
+				));
+			}
+			parse_url(&source.url)?;
+
+			if self.require_signatures && source.public_keys.is_empty() {
+				return Err(Error::DatabaseSourceNotSigned(source.id.clone()));
+			}
+			for public_key in &source.public_keys {
+				signatures::parse_public_key(public_key)?;
+			}
+		}
+
+		if let Some(steam_ids_url) = &self.steam_ids_url {
+			parse_url(steam_ids_url)?;
+		}
+
+		Ok(())
+	}
+}
+
+// Source ids are used as folder names for the database cache, so they need to be safe for that.
+fn is_valid_source_id(source_id: &str) -> bool {
+	!source_id.is_empty()
+		&& source_id.chars().all(|character| {
+			character.is_ascii_lowercase()
+				|| character.is_ascii_digit()
+				|| character == '-'
+				|| character == '_'
+		})
+}
+
+fn get_path() -> Result<PathBuf> {
+	Ok(paths::app_data_path()?.join("database-sources.json"))
+}
+
+// Missing file means we only use the official databases.
+pub fn read() -> Result<DatabaseSources> {
+	let path = get_path()?;
+
+	if !path.is_file() {
+		return Ok(DatabaseSources::official_only());
+	}
+
+	let sources: DatabaseSources = serde_json::from_str(&fs::read_to_string(&path)?)?;
+	sources.validate()?;
+
+	Ok(sources)
+}
+
+pub fn write(sources: &DatabaseSources) -> Result {
+	sources.validate()?;
+
+	let path = get_path()?;
+	fs::create_dir_all(paths::path_parent(&path)?)?;
+	fs::write(&path, serde_json::to_string_pretty(sources)?)?;
+
+	Ok(())
+}
+
+fn parse_url(url: &str) -> Result<Url> {
+	let parsed_url =
+		Url::parse(url).map_err(|error| Error::InvalidUrl(url.to_string(), error.to_string()))?;
+
+	match parsed_url.scheme() {
+		"http" | "https" | "file" => Ok(parsed_url),
+		scheme => Err(Error::InvalidUrl(
+			url.to_string(),
+			format!("unsupported scheme `{scheme}`"),
+		)),
+	}
+}
+
+// Joins with a slash, since `Url::join` would replace the last segment of urls without a trailing slash.
+pub fn join_url(base_url: &str, file_name: &str) -> String {
+	format!("{}/{file_name}", base_url.trim_end_matches('/'))
+}
+
+// Local path if this is a file:// url, so it can be read without going through reqwest.
+pub fn get_file_path(url: &str) -> Result<Option<PathBuf>> {
+	let parsed_url = parse_url(url)?;
+
+	if parsed_url.scheme() != "file" {
+		return Ok(None);
+	}
+
+	parsed_url
+		.to_file_path()
+		.map(Some)
+		.map_err(|()| Error::InvalidUrl(url.to_string(), "not a valid file path".to_string()))
+}
+
+pub async fn get_text(url: &str) -> Result<String> {
+	if let Some(file_path) = get_file_path(url)? {
+		return Ok(fs::read_to_string(file_path)?);
+	}
+
+	Ok(reqwest::get(url).await?.error_for_status()?.text().await?)
+}
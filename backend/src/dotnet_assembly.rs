@@ -0,0 +1,603 @@
+use std::{
+	collections::{
+		HashMap,
+		HashSet,
+	},
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+	sync::Mutex,
+	time::SystemTime,
+};
+
+use goblin::pe::PE;
+use lazy_static::lazy_static;
+use log::error;
+
+use crate::{
+	files,
+	game_engines::unity::UnityScriptingBackend,
+	Result,
+};
+
+// Assemblies that only IL2CPP mods reference, since they're needed to talk to the IL2CPP runtime.
+const IL2CPP_REFERENCES: [&str; 5] = [
+	"Il2CppInterop.Runtime",
+	"UnhollowerBaseLib",
+	"Il2Cppmscorlib",
+	"BepInEx.IL2CPP",
+	"BepInEx.Unity.IL2CPP",
+];
+
+// BepInEx 5 only supports Mono, and its core assembly is just called BepInEx.
+// IL2CPP interop assemblies keep the Unity names, so these only count if nothing above is referenced.
+const MONO_REFERENCES: [&str; 4] = [
+	"BepInEx",
+	"BepInEx.Unity.Mono",
+	"UnityEngine",
+	"UnityEngine.CoreModule",
+];
+
+const METADATA_SIGNATURE: u32 = 0x424A_5342;
+
+type CachedReferences = (SystemTime, Option<Vec<String>>);
+
+lazy_static! {
+	// Local mods get scanned on every refresh, but their dlls rarely change.
+	// Keyed by path, and only used while the file's modified time is the same.
+	static ref REFERENCES_CACHE: Mutex<HashMap<PathBuf, CachedReferences>> =
+		Mutex::new(HashMap::new());
+}
+const ASSEMBLY_REF_TABLE: usize = 0x23;
+
+// Used for coded index tags that don't point to any table.
+const UNUSED_TABLE: usize = 0x3F;
+
+#[derive(Clone, Copy)]
+enum Column {
+	Fixed(usize),
+	String,
+	Guid,
+	Blob,
+	Table(usize),
+	Coded(&'static [usize]),
+}
+
+const TYPE_DEF_OR_REF: &[usize] = &[0x02, 0x01, 0x1B];
+const HAS_CONSTANT: &[usize] = &[0x04, 0x08, 0x17];
+const HAS_CUSTOM_ATTRIBUTE: &[usize] = &[
+	0x06, 0x04, 0x01, 0x02, 0x08, 0x09, 0x0A, 0x00, 0x0E, 0x17, 0x14, 0x11, 0x1A, 0x1B, 0x20, 0x23,
+	0x26, 0x27, 0x28, 0x2A, 0x2C, 0x2B,
+];
+const HAS_FIELD_MARSHAL: &[usize] = &[0x04, 0x08];
+const HAS_DECL_SECURITY: &[usize] = &[0x02, 0x06, 0x20];
+const MEMBER_REF_PARENT: &[usize] = &[0x02, 0x01, 0x1A, 0x06, 0x1B];
+const HAS_SEMANTICS: &[usize] = &[0x14, 0x17];
+const METHOD_DEF_OR_REF: &[usize] = &[0x06, 0x0A];
+const MEMBER_FORWARDED: &[usize] = &[0x04, 0x06];
+const RESOLUTION_SCOPE: &[usize] = &[0x00, 0x1A, 0x23, 0x01];
+const CUSTOM_ATTRIBUTE_TYPE: &[usize] = &[UNUSED_TABLE, UNUSED_TABLE, 0x06, 0x0A, UNUSED_TABLE];
+
+// Columns of every metadata table that comes before AssemblyRef (ECMA-335 II.22).
+// We need the size of each of these tables to know where AssemblyRef starts.
+const TABLE_COLUMNS: [&[Column]; ASSEMBLY_REF_TABLE] = [
+	// Module
+	&[
+		Column::Fixed(2),
+		Column::String,
+		Column::Guid,
+		Column::Guid,
+		Column::Guid,
+	],
+	// TypeRef
+	&[
+		Column::Coded(RESOLUTION_SCOPE),
+		Column::String,
+		Column::String,
+	],
+	// TypeDef
+	&[
+		Column::Fixed(4),
+		Column::String,
+		Column::String,
+		Column::Coded(TYPE_DEF_OR_REF),
+		Column::Table(0x04),
+		Column::Table(0x06),
+	],
+	// FieldPtr
+	&[Column::Table(0x04)],
+	// Field
+	&[Column::Fixed(2), Column::String, Column::Blob],
+	// MethodPtr
+	&[Column::Table(0x06)],
+	// MethodDef
+	&[
+		Column::Fixed(4),
+		Column::Fixed(2),
+		Column::Fixed(2),
+		Column::String,
+		Column::Blob,
+		Column::Table(0x08),
+	],
+	// ParamPtr
+	&[Column::Table(0x08)],
+	// Param
+	&[Column::Fixed(2), Column::Fixed(2), Column::String],
+	// InterfaceImpl
+	&[Column::Table(0x02), Column::Coded(TYPE_DEF_OR_REF)],
+	// MemberRef
+	&[
+		Column::Coded(MEMBER_REF_PARENT),
+		Column::String,
+		Column::Blob,
+	],
+	// Constant
+	&[Column::Fixed(2), Column::Coded(HAS_CONSTANT), Column::Blob],
+	// CustomAttribute
+	&[
+		Column::Coded(HAS_CUSTOM_ATTRIBUTE),
+		Column::Coded(CUSTOM_ATTRIBUTE_TYPE),
+		Column::Blob,
+	],
+	// FieldMarshal
+	&[Column::Coded(HAS_FIELD_MARSHAL), Column::Blob],
+	// DeclSecurity
+	&[
+		Column::Fixed(2),
+		Column::Coded(HAS_DECL_SECURITY),
+		Column::Blob,
+	],
+	// ClassLayout
+	&[Column::Fixed(2), Column::Fixed(4), Column::Table(0x02)],
+	// FieldLayout
+	&[Column::Fixed(4), Column::Table(0x04)],
+	// StandAloneSig
+	&[Column::Blob],
+	// EventMap
+	&[Column::Table(0x02), Column::Table(0x14)],
+	// EventPtr
+	&[Column::Table(0x14)],
+	// Event
+	&[
+		Column::Fixed(2),
+		Column::String,
+		Column::Coded(TYPE_DEF_OR_REF),
+	],
+	// PropertyMap
+	&[Column::Table(0x02), Column::Table(0x17)],
+	// PropertyPtr
+	&[Column::Table(0x17)],
+	// Property
+	&[Column::Fixed(2), Column::String, Column::Blob],
+	// MethodSemantics
+	&[
+		Column::Fixed(2),
+		Column::Table(0x06),
+		Column::Coded(HAS_SEMANTICS),
+	],
+	// MethodImpl
+	&[
+		Column::Table(0x02),
+		Column::Coded(METHOD_DEF_OR_REF),
+		Column::Coded(METHOD_DEF_OR_REF),
+	],
+	// ModuleRef
+	&[Column::String],
+	// TypeSpec
+	&[Column::Blob],
+	// ImplMap
+	&[
+		Column::Fixed(2),
+		Column::Coded(MEMBER_FORWARDED),
+		Column::String,
+		Column::Table(0x1A),
+	],
+	// FieldRVA
+	&[Column::Fixed(4), Column::Table(0x04)],
+	// EncLog
+	&[Column::Fixed(4), Column::Fixed(4)],
+	// EncMap
+	&[Column::Fixed(4)],
+	// Assembly
+	&[
+		Column::Fixed(4),
+		Column::Fixed(8),
+		Column::Fixed(4),
+		Column::Blob,
+		Column::String,
+		Column::String,
+	],
+	// AssemblyProcessor
+	&[Column::Fixed(4)],
+	// AssemblyOS
+	&[Column::Fixed(4), Column::Fixed(4), Column::Fixed(4)],
+];
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+	Some(u16::from_le_bytes(
+		bytes.get(offset..offset + 2)?.try_into().ok()?,
+	))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+	Some(u32::from_le_bytes(
+		bytes.get(offset..offset + 4)?.try_into().ok()?,
+	))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+	Some(u64::from_le_bytes(
+		bytes.get(offset..offset + 8)?.try_into().ok()?,
+	))
+}
+
+fn read_offset(bytes: &[u8], offset: usize) -> Option<usize> {
+	usize::try_from(read_u32(bytes, offset)?).ok()
+}
+
+fn read_index(bytes: &[u8], offset: usize, size: usize) -> Option<usize> {
+	if size == 2 {
+		Some(usize::from(read_u16(bytes, offset)?))
+	} else {
+		read_offset(bytes, offset)
+	}
+}
+
+fn read_null_terminated(bytes: &[u8], offset: usize) -> Option<&[u8]> {
+	let remaining = bytes.get(offset..)?;
+	Some(&remaining[..remaining.iter().position(|byte| *byte == 0)?])
+}
+
+fn rva_to_offset(pe: &PE, rva: u32) -> Option<usize> {
+	let section = pe.sections.iter().find(|section| {
+		rva >= section.virtual_address
+			&& rva - section.virtual_address < section.virtual_size.max(section.size_of_raw_data)
+	})?;
+
+	usize::try_from((rva - section.virtual_address).checked_add(section.pointer_to_raw_data)?).ok()
+}
+
+struct TablesHeader {
+	heap_sizes: u8,
+	row_counts: [usize; 64],
+	tables_start: usize,
+}
+
+impl TablesHeader {
+	fn parse(bytes: &[u8], stream_start: usize) -> Option<Self> {
+		let heap_sizes = *bytes.get(stream_start + 6)?;
+		let valid_tables = read_u64(bytes, stream_start + 8)?;
+
+		let mut row_counts = [0; 64];
+		let mut offset = stream_start + 24;
+
+		for (table, row_count) in row_counts.iter_mut().enumerate() {
+			if valid_tables & (1 << table) != 0 {
+				*row_count = read_offset(bytes, offset)?;
+				offset += 4;
+			}
+		}
+
+		// Some obfuscated or edit-and-continue assemblies have an extra field here.
+		if heap_sizes & 0x40 != 0 {
+			offset += 4;
+		}
+
+		Some(Self {
+			heap_sizes,
+			row_counts,
+			tables_start: offset,
+		})
+	}
+
+	const fn heap_index_size(&self, flag: u8) -> usize {
+		if self.heap_sizes & flag == 0 {
+			2
+		} else {
+			4
+		}
+	}
+
+	fn column_size(&self, column: Column) -> usize {
+		match column {
+			Column::Fixed(size) => size,
+			Column::String => self.heap_index_size(0x01),
+			Column::Guid => self.heap_index_size(0x02),
+			Column::Blob => self.heap_index_size(0x04),
+			Column::Table(table) => {
+				if self.row_counts[table] < 1 << 16 {
+					2
+				} else {
+					4
+				}
+			}
+			Column::Coded(tables) => {
+				let tag_bits = usize::BITS - (tables.len() - 1).leading_zeros();
+				let max_row_count = tables
+					.iter()
+					.map(|table| self.row_counts[*table])
+					.max()
+					.unwrap_or_default();
+
+				if max_row_count < 1 << (16 - tag_bits) {
+					2
+				} else {
+					4
+				}
+			}
+		}
+	}
+
+	fn row_size(&self, columns: &[Column]) -> usize {
+		columns.iter().map(|column| self.column_size(*column)).sum()
+	}
+}
+
+// Reads the names in the AssemblyRef metadata table.
+// Returns None if the file isn't a .NET assembly.
+fn parse_assembly_references(bytes: &[u8], pe: &PE) -> Option<Vec<String>> {
+	let optional_header = pe.header.optional_header?;
+	let clr_runtime_header = optional_header
+		.data_directories
+		.get_clr_runtime_header()
+		.as_ref()?;
+
+	let cli_header = rva_to_offset(pe, clr_runtime_header.virtual_address)?;
+	let metadata = rva_to_offset(pe, read_u32(bytes, cli_header + 8)?)?;
+
+	if read_u32(bytes, metadata)? != METADATA_SIGNATURE {
+		return None;
+	}
+
+	let streams_header = metadata + 16 + read_offset(bytes, metadata + 12)?;
+	let stream_count = read_u16(bytes, streams_header + 2)?;
+
+	let mut tables_stream = None;
+	let mut strings_stream = None;
+	let mut offset = streams_header + 4;
+
+	for _ in 0..stream_count {
+		let stream_start = metadata + read_offset(bytes, offset)?;
+		let name = read_null_terminated(bytes, offset + 8)?;
+
+		match name {
+			b"#~" | b"#-" => tables_stream = Some(stream_start),
+			b"#Strings" => strings_stream = Some(stream_start),
+			_ => {}
+		}
+
+		// Stream names include the null terminator, and are padded to 4 bytes.
+		offset += 8 + (name.len() + 4) / 4 * 4;
+	}
+
+	let tables_header = TablesHeader::parse(bytes, tables_stream?)?;
+	let strings_stream = strings_stream?;
+
+	let assembly_ref_start = tables_header.tables_start
+		+ TABLE_COLUMNS
+			.iter()
+			.enumerate()
+			.map(|(table, columns)| {
+				tables_header.row_counts[table] * tables_header.row_size(columns)
+			})
+			.sum::<usize>();
+
+	// AssemblyRef columns: version (8 bytes), flags (4 bytes), public key blob, name, culture, hash blob.
+	let name_column_offset = 12 + tables_header.column_size(Column::Blob);
+	let string_index_size = tables_header.column_size(Column::String);
+	let row_size =
+		name_column_offset + string_index_size * 2 + tables_header.column_size(Column::Blob);
+
+	(0..tables_header.row_counts[ASSEMBLY_REF_TABLE])
+		.map(|row| {
+			let name_index = read_index(
+				bytes,
+				assembly_ref_start + row * row_size + name_column_offset,
+				string_index_size,
+			)?;
+
+			Some(
+				String::from_utf8_lossy(read_null_terminated(bytes, strings_stream + name_index)?)
+					.to_string(),
+			)
+		})
+		.collect()
+}
+
+fn read_assembly_references(bytes: &[u8]) -> Result<Option<Vec<String>>> {
+	let pe = PE::parse(bytes)?;
+
+	Ok(parse_assembly_references(bytes, &pe))
+}
+
+pub fn get_assembly_references(path: &Path) -> Result<Option<Vec<String>>> {
+	let modified_time = fs::metadata(path)?.modified()?;
+
+	if let Some((cached_time, references)) = REFERENCES_CACHE
+		.lock()
+		.ok()
+		.and_then(|cache| cache.get(path).cloned())
+	{
+		if cached_time == modified_time {
+			return Ok(references);
+		}
+	}
+
+	let references = read_assembly_references(&fs::read(path)?)?;
+
+	if let Ok(mut cache) = REFERENCES_CACHE.lock() {
+		cache.insert(path.to_path_buf(), (modified_time, references.clone()));
+	}
+
+	Ok(references)
+}
+
+// Reads the assembly references of every dll in the folder, ignoring anything that can't be read.
+pub fn get_folder_assembly_references(folder: &Path) -> HashSet<String> {
+	let dll_paths = match files::find_files_with_extension(folder, "dll") {
+		Ok(dll_paths) => dll_paths,
+		Err(err) => {
+			error!(
+				"Failed to find dlls in `{}`. Error: {err}",
+				folder.display()
+			);
+			return HashSet::new();
+		}
+	};
+
+	dll_paths
+		.iter()
+		.filter_map(|dll_path| match get_assembly_references(dll_path) {
+			Ok(references) => references,
+			Err(err) => {
+				error!(
+					"Failed to read assembly references from `{}`. Error: {err}",
+					dll_path.display()
+				);
+				None
+			}
+		})
+		.flatten()
+		.collect()
+}
+
+pub fn get_unity_backend(references: &HashSet<String>) -> Option<UnityScriptingBackend> {
+	if IL2CPP_REFERENCES
+		.iter()
+		.any(|reference| references.contains(*reference))
+	{
+		Some(UnityScriptingBackend::Il2Cpp)
+	} else if MONO_REFERENCES
+		.iter()
+		.any(|reference| references.contains(*reference))
+	{
+		Some(UnityScriptingBackend::Mono)
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use std::{
+		collections::HashSet,
+		fs,
+		path::PathBuf,
+	};
+
+	use super::{
+		get_assembly_references,
+		get_folder_assembly_references,
+		get_unity_backend,
+		read_assembly_references,
+	};
+	use crate::game_engines::unity::UnityScriptingBackend;
+
+	fn get_test_assembly_path(file_name: &str) -> PathBuf {
+		PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+			.join("test-data")
+			.join("dotnet-assemblies")
+			.join(file_name)
+	}
+
+	fn read_references(file_name: &str) -> Option<Vec<String>> {
+		get_assembly_references(&get_test_assembly_path(file_name)).unwrap()
+	}
+
+	fn get_backend(file_name: &str) -> Option<UnityScriptingBackend> {
+		get_unity_backend(&read_references(file_name)?.into_iter().collect())
+	}
+
+	#[test]
+	fn reads_mono_references() {
+		assert_eq!(
+			read_references("mono.dll"),
+			Some(vec![
+				"mscorlib".to_string(),
+				"UnityEngine".to_string(),
+				"BepInEx".to_string(),
+			])
+		);
+		assert_eq!(get_backend("mono.dll"), Some(UnityScriptingBackend::Mono));
+	}
+
+	#[test]
+	fn reads_il2cpp_references() {
+		// Also references UnityEngine.CoreModule, which would mean Mono on its own.
+		assert_eq!(
+			get_backend("il2cpp.dll"),
+			Some(UnityScriptingBackend::Il2Cpp)
+		);
+	}
+
+	#[test]
+	fn reads_plain_references() {
+		assert_eq!(
+			read_references("plain.dll"),
+			Some(vec![
+				"mscorlib".to_string(),
+				"System".to_string(),
+				"System.Core".to_string(),
+			])
+		);
+		assert_eq!(get_backend("plain.dll"), None);
+	}
+
+	#[test]
+	fn ignores_native_dlls() {
+		assert_eq!(read_references("native.dll"), None);
+	}
+
+	#[test]
+	fn reads_every_dll_in_folder() {
+		let references = get_folder_assembly_references(&get_test_assembly_path(""));
+
+		assert!(references.contains("BepInEx"));
+		assert!(references.contains("Il2CppInterop.Runtime"));
+		assert!(references.contains("System.Core"));
+		assert_eq!(
+			get_unity_backend(&references),
+			Some(UnityScriptingBackend::Il2Cpp)
+		);
+		assert_eq!(get_unity_backend(&HashSet::new()), None);
+	}
+
+	#[test]
+	fn handles_truncated_assemblies() {
+		let bytes = fs::read(get_test_assembly_path("mono.dll")).unwrap();
+
+		for length in 0..bytes.len() {
+			// Anything could happen depending on where it's cut, as long as it doesn't panic.
+			if let Ok(Some(references)) = read_assembly_references(&bytes[..length]) {
+				assert!(references.len() <= 3);
+			}
+		}
+	}
+
+	#[test]
+	fn handles_malformed_assemblies() {
+		let bytes = fs::read(get_test_assembly_path("il2cpp.dll")).unwrap();
+
+		assert!(read_assembly_references(b"not an assembly").is_err());
+
+		// Breaking the metadata signature means it's not a .NET assembly.
+		let mut bad_signature = bytes.clone();
+		let signature_offset = bytes
+			.windows(4)
+			.position(|window| window == b"BSJB")
+			.unwrap();
+		bad_signature[signature_offset] = 0;
+		assert_eq!(read_assembly_references(&bad_signature).unwrap(), None);
+
+		for index in 0..bytes.len() {
+			for value in [0x00, 0x7F, 0xFF] {
+				let mut corrupted = bytes.clone();
+				corrupted[index] = value;
+				let _ = read_assembly_references(&corrupted);
+			}
+		}
+	}
+}
@@ -0,0 +1,234 @@
+use std::{
+	fs::{
+		self,
+		File,
+		OpenOptions,
+	},
+	io::{
+		self,
+		Write,
+	},
+	path::{
+		Path,
+		PathBuf,
+	},
+	time::{
+		Duration,
+		Instant,
+	},
+};
+
+use log::{
+	error,
+	info,
+	warn,
+};
+use reqwest::{
+	header::{
+		HeaderMap,
+		ETAG,
+		IF_RANGE,
+		LAST_MODIFIED,
+		RANGE,
+	},
+	StatusCode,
+};
+use sha2::{
+	Digest,
+	Sha256,
+};
+
+use crate::{
+	paths,
+	Error,
+	Result,
+};
+
+const MAX_ATTEMPTS: u32 = 3;
+
+// Tauri events are slow, so we don't want to emit one for every single chunk.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// Downloads to a partial file next to the target, and only moves it into place once it's complete and verified.
+// The partial file is named after the url, so that a failed download can be resumed later,
+// without accidentally resuming it from a different url.
+// The server's ETag or Last-Modified is saved next to the partial file, and sent with If-Range when resuming,
+// so that a file that changed on the server gets downloaded from scratch instead of being appended to the old bytes.
+// The progress callback receives the downloaded byte count, and the total byte count if the server tells us.
+pub async fn download_file<F>(
+	url: &str,
+	target_path: &Path,
+	sha256: Option<&str>,
+	on_progress: F,
+) -> Result
+where
+	F: Fn(u64, Option<u64>) + Send + Sync,
+{
+	let partial_path = get_partial_path(url, target_path)?;
+	let validator_path = get_validator_path(&partial_path);
+
+	let mut attempt = 1;
+	while let Err(error) = download_partial(url, &partial_path, &validator_path, &on_progress).await
+	{
+		if attempt >= MAX_ATTEMPTS || !is_retryable(&error) {
+			return Err(error);
+		}
+
+		error!("Failed to download `{url}` (attempt {attempt}/{MAX_ATTEMPTS}), retrying. Error: {error}");
+		attempt += 1;
+	}
+
+	if let Some(expected_hash) = sha256 {
+		let actual_hash = get_file_sha256(&partial_path)?;
+
+		if !actual_hash.eq_ignore_ascii_case(expected_hash) {
+			// Can't know which part of the file is corrupted, so there's nothing worth resuming.
+			fs::remove_file(&partial_path)?;
+			remove_validator(&validator_path)?;
+
+			return Err(Error::DownloadChecksumMismatch(
+				url.to_string(),
+				expected_hash.to_string(),
+				actual_hash,
+			));
+		}
+	} else {
+		warn!("No SHA-256 given for `{url}`, so the download can't be verified.");
+	}
+
+	fs::rename(&partial_path, target_path)?;
+	remove_validator(&validator_path)?;
+
+	Ok(())
+}
+
+// Errors like a 404 would just happen again, so only connection problems and server side errors are worth retrying.
+fn is_retryable(error: &Error) -> bool {
+	let Error::Reqwest(request_error) = error else {
+		return false;
+	};
+
+	request_error.status().map_or_else(
+		|| request_error.is_connect() || request_error.is_timeout() || request_error.is_body(),
+		|status| status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+	)
+}
+
+// Weak ETags aren't allowed in If-Range, so those fall back to Last-Modified.
+fn get_validator(headers: &HeaderMap) -> Option<String> {
+	headers
+		.get(ETAG)
+		.and_then(|value| value.to_str().ok())
+		.filter(|etag| !etag.starts_with("W/"))
+		.or_else(|| {
+			headers
+				.get(LAST_MODIFIED)
+				.and_then(|value| value.to_str().ok())
+		})
+		.map(ToString::to_string)
+}
+
+fn remove_validator(validator_path: &Path) -> Result {
+	if validator_path.is_file() {
+		fs::remove_file(validator_path)?;
+	}
+
+	Ok(())
+}
+
+async fn download_partial<F>(
+	url: &str,
+	partial_path: &Path,
+	validator_path: &Path,
+	on_progress: &F,
+) -> Result
+where
+	F: Fn(u64, Option<u64>) + Send + Sync,
+{
+	// Without a validator there's no way to know if the partial file still matches the server's file.
+	let validator = fs::read_to_string(validator_path).ok();
+	let downloaded_length = if validator.is_some() {
+		fs::metadata(partial_path).map_or(0, |metadata| metadata.len())
+	} else {
+		0
+	};
+
+	let mut request = reqwest::Client::new().get(url);
+	if let (Some(validator), true) = (&validator, downloaded_length > 0) {
+		info!("Resuming download of `{url}` from byte {downloaded_length}");
+		request = request
+			.header(RANGE, format!("bytes={downloaded_length}-"))
+			.header(IF_RANGE, validator);
+	}
+
+	let response = request.send().await?;
+
+	if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && downloaded_length > 0 {
+		// The validator still matched, so the partial file already has everything the server has to give.
+		return Ok(());
+	}
+
+	let mut response = response.error_for_status()?;
+
+	match get_validator(response.headers()) {
+		Some(new_validator) => fs::write(validator_path, new_validator)?,
+		None => remove_validator(validator_path)?,
+	}
+
+	// If-Range makes the server send the whole thing if the file changed, and servers are also allowed
+	// to ignore the range header completely. Either way we need to start over instead of appending.
+	let (mut file, mut current_length) = if response.status() == StatusCode::PARTIAL_CONTENT {
+		(
+			OpenOptions::new().append(true).open(partial_path)?,
+			downloaded_length,
+		)
+	} else {
+		(File::create(partial_path)?, 0)
+	};
+
+	let total_length = response
+		.content_length()
+		.map(|content_length| content_length + current_length);
+
+	on_progress(current_length, total_length);
+	let mut last_progress_time = Instant::now();
+
+	while let Some(chunk) = response.chunk().await? {
+		file.write_all(&chunk)?;
+		current_length += u64::try_from(chunk.len()).unwrap_or_default();
+
+		if last_progress_time.elapsed() >= PROGRESS_INTERVAL {
+			on_progress(current_length, total_length);
+			last_progress_time = Instant::now();
+		}
+	}
+
+	file.flush()?;
+	on_progress(current_length, total_length);
+
+	Ok(())
+}
+
+// Uses sha256 since it needs to give the same name for the same url across app versions.
+fn get_partial_path(url: &str, target_path: &Path) -> Result<PathBuf> {
+	let url_hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+
+	let file_name = target_path
+		.file_name()
+		.ok_or_else(|| Error::PathParseFailure(target_path.to_path_buf()))?
+		.to_string_lossy();
+
+	Ok(paths::path_parent(target_path)?.join(format!("{file_name}.{}.part", &url_hash[..16])))
+}
+
+fn get_validator_path(partial_path: &Path) -> PathBuf {
+	partial_path.with_extension("part-validator")
+}
+
+fn get_file_sha256(path: &Path) -> Result<String> {
+	let mut file = File::open(path)?;
+	let mut hasher = Sha256::new();
+	io::copy(&mut file, &mut hasher)?;
+
+	Ok(format!("{:x}", hasher.finalize()))
+}
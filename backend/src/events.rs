@@ -12,9 +12,12 @@ serializable_enum!(AppEvent {
 	SyncModLoaders,
 	SyncLocalMods,
 	SyncRemoteMods,
+	SyncRunningRunnables,
 	ExecutedProviderCommand,
 	GameAdded,
 	GameRemoved,
+	ModProgress,
+	RunnableExited,
 	Error,
 });
 
@@ -1,15 +1,41 @@
 use std::{
-	fs,
-	path::Path,
+	fs::{
+		self,
+		File,
+	},
+	io::{
+		self,
+		Read,
+	},
+	path::{
+		Path,
+		PathBuf,
+	},
 };
 
-use crate::Result;
+use log::{
+	debug,
+	info,
+};
+use zip::ZipArchive;
+
+use crate::{
+	serializable_struct,
+	Error,
+	Result,
+};
 
 pub fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result {
 	fs::create_dir_all(&dst)?;
 	for entry in fs::read_dir(src)? {
 		let entry = entry?;
 		let file_type = entry.file_type()?;
+		if file_type.is_symlink() {
+			return Err(Error::UnsafePath(
+				entry.path(),
+				"symlinks aren't allowed".to_string(),
+			));
+		}
 		if file_type.is_dir() {
 			copy_dir_all(entry.path(), dst.as_ref().join(entry.file_name()))?;
 		} else {
@@ -18,3 +44,233 @@ pub fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result {
 	}
 	Ok(())
 }
+
+// Searches the folder and all its subfolders.
+pub fn find_files_with_extension(folder: &Path, extension: &str) -> Result<Vec<PathBuf>> {
+	let mut found_files = Vec::new();
+
+	for entry in fs::read_dir(folder)? {
+		let path = entry?.path();
+
+		if path.is_dir() {
+			found_files.extend(find_files_with_extension(&path, extension)?);
+		} else if path
+			.extension()
+			.is_some_and(|path_extension| path_extension.eq_ignore_ascii_case(extension))
+		{
+			found_files.push(path);
+		}
+	}
+
+	Ok(found_files)
+}
+
+// Mod archives are nowhere near this big, so anything bigger is most likely a broken archive, or a zip bomb.
+const MAX_ENTRY_SIZE: u64 = 512 * 1024 * 1024;
+const MAX_ARCHIVE_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+
+// File type bits of a unix mode, and the value they have for symlinks.
+const UNIX_FILE_TYPE_MASK: u32 = 0o170_000;
+const UNIX_SYMLINK_TYPE: u32 = 0o120_000;
+
+// Something that will be written to disk when extracting an archive.
+serializable_struct!(ArchiveEntry {
+	// Where the entry will be written, inside the extraction target.
+	pub path: PathBuf,
+	pub is_dir: bool,
+	// Uncompressed size, as declared by the archive.
+	pub size: u64,
+});
+
+// Doesn't rely on `Path` alone, since archives made on Windows can use backslashes,
+// which wouldn't be treated as separators when extracting on Linux.
+fn get_unsafe_path_reason(relative_path: &str) -> Option<&'static str> {
+	if relative_path.starts_with(['/', '\\']) || Path::new(relative_path).has_root() {
+		return Some("absolute paths aren't allowed");
+	}
+
+	if relative_path.contains(':') {
+		return Some("drive letters and colons aren't allowed");
+	}
+
+	if relative_path
+		.split(['/', '\\'])
+		.any(|component| component == "..")
+	{
+		return Some("paths can't go outside of the target folder");
+	}
+
+	None
+}
+
+// For names from somewhere we don't trust that end up as a single file or folder name, like mod ids.
+pub fn is_safe_file_name(name: &str) -> bool {
+	!name.trim().is_empty()
+		&& name != "."
+		&& !name.contains(['/', '\\'])
+		&& get_unsafe_path_reason(name).is_none()
+}
+
+// Joins a relative path that came from somewhere we don't trust, like a remote database,
+// making sure the result stays inside the base folder.
+pub fn join_safe_subpath(base_path: &Path, relative_path: &Path) -> Result<PathBuf> {
+	let relative_path_text = relative_path.to_string_lossy();
+
+	if let Some(reason) = get_unsafe_path_reason(&relative_path_text) {
+		return Err(Error::UnsafePath(
+			relative_path.to_path_buf(),
+			reason.to_string(),
+		));
+	}
+
+	let joined_path = base_path.join(relative_path);
+
+	// Also catches symlinks that point outside, if the folders already exist.
+	if let (Ok(canonical_base), Ok(canonical_joined)) =
+		(base_path.canonicalize(), joined_path.canonicalize())
+	{
+		if !canonical_joined.starts_with(canonical_base) {
+			return Err(Error::UnsafePath(
+				relative_path.to_path_buf(),
+				"path resolves to somewhere outside of the target folder".to_string(),
+			));
+		}
+	}
+
+	Ok(joined_path)
+}
+
+// Reads the whole archive and checks every entry, without writing anything.
+fn get_archive_entries(
+	archive: &mut ZipArchive<File>,
+	archive_path: &Path,
+	target_path: &Path,
+) -> Result<Vec<ArchiveEntry>> {
+	let mut entries = Vec::new();
+	let mut total_size: u64 = 0;
+
+	for index in 0..archive.len() {
+		let entry = archive.by_index(index)?;
+		let entry_name = entry.name().to_string();
+
+		let unsafe_entry_error = |reason: &str| {
+			Error::UnsafeArchiveEntry(
+				archive_path.to_path_buf(),
+				entry_name.clone(),
+				reason.to_string(),
+			)
+		};
+
+		if let Some(reason) = get_unsafe_path_reason(&entry_name) {
+			return Err(unsafe_entry_error(reason));
+		}
+
+		let enclosed_name = entry
+			.enclosed_name()
+			.ok_or_else(|| unsafe_entry_error("path isn't valid"))?;
+
+		if entry
+			.unix_mode()
+			.is_some_and(|mode| mode & UNIX_FILE_TYPE_MASK == UNIX_SYMLINK_TYPE)
+		{
+			return Err(unsafe_entry_error("symlinks aren't allowed"));
+		}
+
+		if entry.size() > MAX_ENTRY_SIZE {
+			return Err(unsafe_entry_error("entry is too big"));
+		}
+
+		total_size = total_size.saturating_add(entry.size());
+		if total_size > MAX_ARCHIVE_SIZE {
+			return Err(Error::ArchiveTooBig(
+				archive_path.to_path_buf(),
+				MAX_ARCHIVE_SIZE,
+			));
+		}
+
+		entries.push(ArchiveEntry {
+			path: target_path.join(enclosed_name),
+			is_dir: entry.is_dir(),
+			size: entry.size(),
+		});
+	}
+
+	Ok(entries)
+}
+
+// Same as ZipArchive::extract, but reports how many entries have been extracted so far.
+pub fn extract_zip<F>(archive_path: &Path, target_path: &Path, on_progress: F) -> Result
+where
+	F: Fn(u64, u64),
+{
+	extract_zip_with(archive_path, target_path, |_| Ok(()), on_progress)
+}
+
+// Every archive extraction goes through here. The whole archive gets checked before anything is written,
+// so an unsafe archive is rejected without leaving half of its files behind.
+// `before_write` gets called with every entry that will be written, before any of them are.
+// Returning an error from it stops the extraction before anything is written.
+pub fn extract_zip_with<W, F>(
+	archive_path: &Path,
+	target_path: &Path,
+	before_write: W,
+	on_progress: F,
+) -> Result
+where
+	W: FnOnce(&[ArchiveEntry]) -> Result,
+	F: Fn(u64, u64),
+{
+	let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+	let entries = get_archive_entries(&mut archive, archive_path, target_path)?;
+
+	info!(
+		"Extracting {} entries from {} to {}",
+		entries.len(),
+		archive_path.display(),
+		target_path.display()
+	);
+	for entry in &entries {
+		debug!("Extracting {}", entry.path.display());
+	}
+
+	before_write(&entries)?;
+
+	let entry_count = u64::try_from(entries.len()).unwrap_or_default();
+
+	for (index, entry) in entries.iter().enumerate() {
+		let mut zip_entry = archive.by_index(index)?;
+
+		if entry.is_dir {
+			fs::create_dir_all(&entry.path)?;
+		} else {
+			if let Some(parent) = entry.path.parent() {
+				fs::create_dir_all(parent)?;
+			}
+
+			// The declared size could be a lie, so we stop reading right after it.
+			let written_size = io::copy(
+				&mut Read::by_ref(&mut zip_entry).take(entry.size.saturating_add(1)),
+				&mut File::create(&entry.path)?,
+			)?;
+
+			if written_size > entry.size {
+				fs::remove_file(&entry.path)?;
+				return Err(Error::UnsafeArchiveEntry(
+					archive_path.to_path_buf(),
+					zip_entry.name().to_string(),
+					"entry is bigger than its declared size".to_string(),
+				));
+			}
+		}
+
+		#[cfg(unix)]
+		if let Some(mode) = zip_entry.unix_mode() {
+			use std::os::unix::fs::PermissionsExt;
+			fs::set_permissions(&entry.path, fs::Permissions::from_mode(mode))?;
+		}
+
+		on_progress(u64::try_from(index + 1).unwrap_or_default(), entry_count);
+	}
+
+	Ok(())
+}
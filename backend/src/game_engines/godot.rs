@@ -0,0 +1,143 @@
+use std::{
+	fs::File,
+	io::{
+		Read,
+		Seek,
+		SeekFrom,
+	},
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::error;
+
+use crate::{
+	game_engines::game_engine::{
+		GameEngine,
+		GameEngineBrand,
+		GameEngineVersion,
+	},
+	game_executable::{
+		get_os_and_architecture,
+		GameExecutable,
+	},
+	result::Result,
+};
+
+// Every Godot pck file starts with these bytes.
+const PCK_MAGIC: [u8; 4] = *b"GDPC";
+
+fn read_u32(file: &mut File) -> Result<u32> {
+	let mut bytes = [0; 4];
+	file.read_exact(&mut bytes)?;
+	Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(file: &mut File) -> Result<u64> {
+	let mut bytes = [0; 8];
+	file.read_exact(&mut bytes)?;
+	Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_magic(file: &mut File) -> Result<bool> {
+	let mut bytes = [0; 4];
+	file.read_exact(&mut bytes)?;
+	Ok(bytes == PCK_MAGIC)
+}
+
+// The pck header is: magic, pack format version, then the engine major, minor and patch versions.
+fn read_pck_version(file: &mut File, pck_start: u64) -> Result<Option<GameEngineVersion>> {
+	file.seek(SeekFrom::Start(pck_start))?;
+
+	if !read_magic(file)? {
+		return Ok(None);
+	}
+
+	let _pack_format_version = read_u32(file)?;
+	let major = read_u32(file)?;
+	let minor = read_u32(file)?;
+	let patch = read_u32(file)?;
+
+	Ok(Some(GameEngineVersion {
+		major,
+		minor,
+		patch,
+		suffix: None,
+		display: format!("{major}.{minor}.{patch}"),
+	}))
+}
+
+// When the pck is embedded in the executable, the file ends with the pck size followed by the magic.
+fn get_embedded_pck_start(file: &mut File) -> Result<Option<u64>> {
+	let file_length = file.seek(SeekFrom::End(0))?;
+
+	if file_length < 12 {
+		return Ok(None);
+	}
+
+	file.seek(SeekFrom::End(-4))?;
+	if !read_magic(file)? {
+		return Ok(None);
+	}
+
+	file.seek(SeekFrom::End(-12))?;
+	let pck_size = read_u64(file)?;
+
+	Ok((file_length - 12).checked_sub(pck_size))
+}
+
+// Non-embedded pck files have the same name as the executable.
+fn get_adjacent_pck_path(game_path: &Path) -> Option<PathBuf> {
+	let pck_path = game_path.with_extension("pck");
+
+	if pck_path.is_file() {
+		Some(pck_path)
+	} else {
+		None
+	}
+}
+
+fn get_version(game_path: &Path) -> Result<Option<GameEngineVersion>> {
+	if let Some(pck_path) = get_adjacent_pck_path(game_path) {
+		return read_pck_version(&mut File::open(pck_path)?, 0);
+	}
+
+	let mut file = File::open(game_path)?;
+
+	get_embedded_pck_start(&mut file)?.map_or_else(
+		|| Ok(None),
+		|pck_start| read_pck_version(&mut file, pck_start),
+	)
+}
+
+pub fn get_executable(game_path: &Path) -> Option<GameExecutable> {
+	let version = match get_version(game_path) {
+		Ok(Some(version)) => version,
+		Ok(None) => return None,
+		Err(err) => {
+			error!(
+				"Failed to read Godot pck for `{}`. Error: {}",
+				game_path.display(),
+				err
+			);
+			return None;
+		}
+	};
+
+	let (operating_system, architecture) =
+		get_os_and_architecture(game_path).unwrap_or((None, None));
+
+	Some(GameExecutable {
+		path: game_path.to_path_buf(),
+		name: game_path.file_name()?.to_string_lossy().to_string(),
+		architecture,
+		operating_system,
+		scripting_backend: None,
+		engine: Some(GameEngine {
+			brand: GameEngineBrand::Godot,
+			version: Some(version),
+		}),
+	})
+}
@@ -1,3 +1,4 @@
 pub mod game_engine;
+pub mod godot;
 pub mod unity;
 pub mod unreal;
@@ -15,6 +15,7 @@ use log::error;
 use crate::{
 	game_engines::{
 		game_engine::GameEngine,
+		godot,
 		unity::{
 			self,
 			UnityScriptingBackend,
@@ -92,6 +93,8 @@ impl GameExecutable {
 	pub fn new(path: &Path) -> Option<Self> {
 		let normalized_path = normalize_path(path);
 
-		unity::get_executable(&normalized_path).or_else(|| unreal::get_executable(&normalized_path))
+		unity::get_executable(&normalized_path)
+			.or_else(|| unreal::get_executable(&normalized_path))
+			.or_else(|| godot::get_executable(&normalized_path))
 	}
 }
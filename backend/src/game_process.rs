@@ -0,0 +1,188 @@
+use std::{
+	path::Path,
+	thread,
+	time::{
+		Duration,
+		Instant,
+	},
+};
+
+use crate::{
+	Error,
+	Result,
+};
+
+// How often we look for the game process while waiting for it to start.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Wine shows Windows paths for the processes it runs, so we also compare just the file name.
+fn is_same_executable_name(process_path: &str, executable_path: &Path) -> bool {
+	let Some(executable_name) = executable_path.file_name().and_then(|name| name.to_str()) else {
+		return false;
+	};
+
+	process_path
+		.rsplit(['/', '\\'])
+		.next()
+		.is_some_and(|process_name| process_name.eq_ignore_ascii_case(executable_name))
+}
+
+// Finds the id of a running process that was started from the given executable.
+#[cfg(not(target_os = "windows"))]
+pub fn find_process_id(executable_path: &Path) -> Option<u32> {
+	use std::fs;
+
+	let canonical_executable_path = executable_path.canonicalize().ok()?;
+
+	fs::read_dir("/proc").ok()?.flatten().find_map(|entry| {
+		let process_id = entry.file_name().to_str()?.parse::<u32>().ok()?;
+		let process_folder = entry.path();
+
+		if fs::read_link(process_folder.join("exe"))
+			.is_ok_and(|process_path| process_path == canonical_executable_path)
+		{
+			return Some(process_id);
+		}
+
+		// For games running through Wine, the executable is the Wine loader,
+		// but Wine replaces the first command line argument with the game executable.
+		// Other arguments are ignored, since they can mention the game without being the game.
+		fs::read_to_string(process_folder.join("cmdline"))
+			.ok()?
+			.split('\0')
+			.next()
+			.is_some_and(|argument| is_same_executable_name(argument, executable_path))
+			.then_some(process_id)
+	})
+}
+
+#[cfg(target_os = "windows")]
+pub fn find_process_id(executable_path: &Path) -> Option<u32> {
+	use std::{
+		ffi::OsString,
+		mem,
+		os::windows::ffi::OsStringExt,
+		path::PathBuf,
+	};
+
+	use winapi::um::{
+		handleapi::{
+			CloseHandle,
+			INVALID_HANDLE_VALUE,
+		},
+		processthreadsapi::OpenProcess,
+		tlhelp32::{
+			CreateToolhelp32Snapshot,
+			Process32FirstW,
+			Process32NextW,
+			PROCESSENTRY32W,
+			TH32CS_SNAPPROCESS,
+		},
+		winbase::QueryFullProcessImageNameW,
+		winnt::PROCESS_QUERY_LIMITED_INFORMATION,
+	};
+
+	fn wide_to_string(wide: &[u16]) -> String {
+		let length = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
+		OsString::from_wide(&wide[..length])
+			.to_string_lossy()
+			.to_string()
+	}
+
+	// Canonical paths on Windows have a verbatim prefix that the process paths don't have,
+	// and Windows paths aren't case sensitive, so both sides go through this before comparing.
+	fn get_comparable_path(path: &Path) -> String {
+		let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+		let path_text = path.to_string_lossy();
+
+		let without_prefix = path_text.strip_prefix(r"\\?\UNC\").map_or_else(
+			|| {
+				path_text
+					.strip_prefix(r"\\?\")
+					.unwrap_or(&path_text)
+					.to_string()
+			},
+			|unc_path| format!(r"\\{unc_path}"),
+		);
+
+		without_prefix.to_lowercase()
+	}
+
+	// Returns None if we aren't allowed to read the process path, which is normal for some system processes.
+	fn get_process_path(process_id: u32) -> Option<PathBuf> {
+		unsafe {
+			let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, process_id);
+			if process_handle.is_null() {
+				return None;
+			}
+
+			let mut buffer = [0u16; 1024];
+			let mut length = u32::try_from(buffer.len()).ok()?;
+			let success =
+				QueryFullProcessImageNameW(process_handle, 0, buffer.as_mut_ptr(), &mut length);
+			CloseHandle(process_handle);
+
+			(success != 0).then(|| PathBuf::from(wide_to_string(&buffer)))
+		}
+	}
+
+	unsafe {
+		let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+		if snapshot == INVALID_HANDLE_VALUE {
+			return None;
+		}
+
+		let mut entry: PROCESSENTRY32W = mem::zeroed();
+		entry.dwSize = u32::try_from(mem::size_of::<PROCESSENTRY32W>()).ok()?;
+
+		let comparable_executable_path = get_comparable_path(executable_path);
+		let mut process_id = None;
+		let mut has_entry = Process32FirstW(snapshot, &mut entry) != 0;
+
+		while has_entry {
+			if is_same_executable_name(&wide_to_string(&entry.szExeFile), executable_path) {
+				let is_same_path = get_process_path(entry.th32ProcessID)
+					.map_or(true, |process_path| {
+						get_comparable_path(&process_path) == comparable_executable_path
+					});
+
+				if is_same_path {
+					process_id = Some(entry.th32ProcessID);
+					break;
+				}
+			}
+
+			has_entry = Process32NextW(snapshot, &mut entry) != 0;
+		}
+
+		CloseHandle(snapshot);
+
+		process_id
+	}
+}
+
+// Blocks until a process of the given executable is running, or until the timeout.
+// Calls `on_wait` with how long we've been waiting every time we look for the process.
+pub fn wait_for_process_id<F>(executable_path: &Path, timeout: Duration, on_wait: F) -> Result<u32>
+where
+	F: Fn(Duration),
+{
+	let start = Instant::now();
+
+	loop {
+		if let Some(process_id) = find_process_id(executable_path) {
+			return Ok(process_id);
+		}
+
+		let elapsed = start.elapsed();
+		if elapsed >= timeout {
+			return Err(Error::GameProcessNotFound(
+				executable_path.to_path_buf(),
+				timeout.as_secs(),
+			));
+		}
+
+		on_wait(elapsed);
+		thread::sleep(WAIT_POLL_INTERVAL);
+	}
+}
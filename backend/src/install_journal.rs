@@ -0,0 +1,527 @@
+use std::{
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::error;
+
+use crate::{
+	files,
+	paths::{
+		self,
+		hash_path,
+	},
+	serializable_enum,
+	serializable_struct,
+	Error,
+	Result,
+};
+
+serializable_enum!(JournalEntryKind {
+	CreatedFile,
+	ReplacedFile,
+	CreatedFolder,
+	Renamed,
+});
+
+serializable_struct!(JournalEntry {
+	pub kind: JournalEntryKind,
+	pub path: PathBuf,
+	pub backup_path: Option<PathBuf>,
+	// Where a renamed file or folder was before, so undoing can move it back.
+	#[serde(default)]
+	pub renamed_from: Option<PathBuf>,
+	// Increases with every entry, so checkpoints still work after older entries are removed.
+	#[serde(default)]
+	pub sequence: u64,
+});
+
+serializable_struct!(JournalData {
+	pub entries: Vec<JournalEntry>,
+	#[serde(default)]
+	pub next_sequence: u64,
+});
+
+// Keeps track of every file and folder Rai Pal writes while installing things into a game,
+// so that a failed install can be rolled back, and the game can be restored to how it was before.
+// Only the first write to each path is recorded, since that's the one that matters for restoring.
+pub struct InstallJournal {
+	folder_path: PathBuf,
+	data: JournalData,
+}
+
+impl InstallJournal {
+	pub fn open(folder_path: &Path) -> Result<Self> {
+		let journal_path = get_journal_path(folder_path);
+
+		let mut data: JournalData = if journal_path.is_file() {
+			serde_json::from_str(&fs::read_to_string(&journal_path)?)?
+		} else {
+			JournalData {
+				entries: Vec::new(),
+				next_sequence: 0,
+			}
+		};
+
+		// Journals written before entries had a sequence get one based on their order.
+		if data.next_sequence == 0 {
+			for (sequence, entry) in (0..).zip(data.entries.iter_mut()) {
+				entry.sequence = sequence;
+			}
+			data.next_sequence = u64::try_from(data.entries.len()).unwrap_or_default();
+		}
+
+		Ok(Self {
+			folder_path: folder_path.to_path_buf(),
+			data,
+		})
+	}
+
+	// Point to roll back to if something fails. Stays valid across different instances of the journal,
+	// so a checkpoint can be taken before a bunch of operations that each open the journal themselves.
+	pub const fn checkpoint(&self) -> u64 {
+		self.data.next_sequence
+	}
+
+	fn is_recorded(&self, path: &Path) -> bool {
+		self.data.entries.iter().any(|entry| entry.path == path)
+	}
+
+	fn push_entry(&mut self, entry: JournalEntry) -> Result {
+		self.data.entries.push(JournalEntry {
+			sequence: self.data.next_sequence,
+			..entry
+		});
+		self.data.next_sequence += 1;
+		self.save()
+	}
+
+	fn save(&self) -> Result {
+		fs::create_dir_all(&self.folder_path)?;
+		fs::write(
+			get_journal_path(&self.folder_path),
+			serde_json::to_string_pretty(&self.data)?,
+		)?;
+
+		Ok(())
+	}
+
+	// Creates a folder (and any missing parents), recording each folder that didn't exist yet.
+	pub fn create_dir_all(&mut self, path: &Path) -> Result {
+		let missing_folders: Vec<_> = path
+			.ancestors()
+			.take_while(|ancestor| !ancestor.exists())
+			.map(Path::to_path_buf)
+			.collect();
+
+		// Outermost folder first, so rolling back in reverse removes the innermost first.
+		for folder in missing_folders.into_iter().rev() {
+			fs::create_dir(&folder)?;
+			if !self.is_recorded(&folder) {
+				self.push_entry(JournalEntry {
+					kind: JournalEntryKind::CreatedFolder,
+					path: folder,
+					backup_path: None,
+					renamed_from: None,
+					sequence: 0,
+				})?;
+			}
+		}
+
+		Ok(())
+	}
+
+	// Needs to be called right before writing to a file.
+	// If the file already exists, a backup is made so it can be restored later.
+	pub fn record_file(&mut self, path: &Path) -> Result {
+		self.create_dir_all(paths::path_parent(path)?)?;
+
+		if self.is_recorded(path) {
+			return Ok(());
+		}
+
+		if path.is_file() {
+			let backup_path = self.folder_path.join("backups").join(hash_path(path));
+			fs::create_dir_all(paths::path_parent(&backup_path)?)?;
+			fs::copy(path, &backup_path)?;
+
+			self.push_entry(JournalEntry {
+				kind: JournalEntryKind::ReplacedFile,
+				path: path.to_path_buf(),
+				backup_path: Some(backup_path),
+				renamed_from: None,
+				sequence: 0,
+			})
+		} else {
+			self.push_entry(JournalEntry {
+				kind: JournalEntryKind::CreatedFile,
+				path: path.to_path_buf(),
+				backup_path: None,
+				renamed_from: None,
+				sequence: 0,
+			})
+		}
+	}
+
+	pub fn write_file<C: AsRef<[u8]>>(&mut self, path: &Path, contents: C) -> Result {
+		self.record_file(path)?;
+		fs::write(path, contents)?;
+
+		Ok(())
+	}
+
+	pub fn copy_file(&mut self, from: &Path, to: &Path) -> Result {
+		self.record_file(to)?;
+		fs::copy(from, to)?;
+
+		Ok(())
+	}
+
+	// Renames are always recorded, even for paths that were already recorded,
+	// since they need to be undone in order for the files to end up back where they started.
+	// Works for folders too.
+	pub fn rename_file(&mut self, from: &Path, to: &Path) -> Result {
+		if to.is_file() {
+			self.record_file(to)?;
+		} else {
+			self.create_dir_all(paths::path_parent(to)?)?;
+		}
+
+		fs::rename(from, to)?;
+
+		self.push_entry(JournalEntry {
+			kind: JournalEntryKind::Renamed,
+			path: to.to_path_buf(),
+			backup_path: None,
+			renamed_from: Some(from.to_path_buf()),
+			sequence: 0,
+		})
+	}
+
+	// Same as files::copy_dir_all, but records everything that gets written.
+	// Returns the paths of all the files that were copied.
+	pub fn copy_dir_all(&mut self, src: &Path, dst: &Path) -> Result<Vec<PathBuf>> {
+		let mut copied_files = Vec::new();
+
+		self.create_dir_all(dst)?;
+		for entry in fs::read_dir(src)? {
+			let entry = entry?;
+			let entry_destination = dst.join(entry.file_name());
+			let file_type = entry.file_type()?;
+			if file_type.is_symlink() {
+				return Err(Error::UnsafePath(
+					entry.path(),
+					"symlinks aren't allowed".to_string(),
+				));
+			}
+			if file_type.is_dir() {
+				copied_files.extend(self.copy_dir_all(&entry.path(), &entry_destination)?);
+			} else {
+				self.copy_file(&entry.path(), &entry_destination)?;
+				copied_files.push(entry_destination);
+			}
+		}
+
+		Ok(copied_files)
+	}
+
+	// Same as files::extract_zip, but records everything that gets written.
+	// `on_entries` gets the full list of entries before any of them are written.
+	pub fn extract_zip<E, F>(
+		&mut self,
+		archive_path: &Path,
+		target_path: &Path,
+		on_entries: E,
+		on_progress: F,
+	) -> Result
+	where
+		E: FnOnce(&[files::ArchiveEntry]),
+		F: Fn(u64, u64),
+	{
+		files::extract_zip_with(
+			archive_path,
+			target_path,
+			|entries| {
+				on_entries(entries);
+
+				for entry in entries {
+					if entry.is_dir {
+						self.create_dir_all(&entry.path)?;
+					} else {
+						self.record_file(&entry.path)?;
+					}
+				}
+
+				Ok(())
+			},
+			on_progress,
+		)
+	}
+
+	// Removes a file that was written by Rai Pal.
+	// If that file replaced one that was there before, the original file is restored instead.
+	// Files that only got here by being renamed are just removed, since moving them back isn't removing them.
+	pub fn remove_file(&mut self, path: &Path) -> Result {
+		let (removed_entries, kept_entries): (Vec<_>, Vec<_>) = self
+			.data
+			.entries
+			.drain(..)
+			.partition(|entry| entry.path == path);
+		self.data.entries = kept_entries;
+
+		match removed_entries
+			.iter()
+			.find(|entry| entry.kind != JournalEntryKind::Renamed)
+		{
+			Some(entry) => undo_entry(entry)?,
+			None => {
+				if path.is_file() {
+					fs::remove_file(path)?;
+				}
+			}
+		}
+
+		self.save()
+	}
+
+	// Undoes everything recorded inside the given folder, including things renamed out of it.
+	// Anything in there that Rai Pal didn't write is left alone.
+	pub fn remove_recorded_in(&mut self, folder_path: &Path) -> Result {
+		let (removed_entries, kept_entries): (Vec<_>, Vec<_>) =
+			self.data.entries.drain(..).partition(|entry| {
+				entry.path.starts_with(folder_path)
+					|| entry
+						.renamed_from
+						.as_ref()
+						.is_some_and(|renamed_from| renamed_from.starts_with(folder_path))
+			});
+		self.data.entries = kept_entries;
+
+		undo_entries(&removed_entries);
+
+		self.save()
+	}
+
+	// Undoes everything recorded after the given checkpoint, most recent first.
+	pub fn rollback(&mut self, checkpoint: u64) -> Result {
+		let first_index = self
+			.data
+			.entries
+			.partition_point(|entry| entry.sequence < checkpoint);
+		let entries = self.data.entries.split_off(first_index);
+
+		undo_entries(&entries);
+
+		self.save()
+	}
+
+	pub const fn is_empty(&self) -> bool {
+		self.data.entries.is_empty()
+	}
+
+	// Reverts every change ever recorded in this journal, and then deletes the journal itself.
+	pub fn restore_all(mut self) -> Result {
+		self.rollback(0)?;
+
+		let backups_path = self.folder_path.join("backups");
+		if backups_path.is_dir() {
+			fs::remove_dir_all(backups_path)?;
+		}
+
+		let journal_path = get_journal_path(&self.folder_path);
+		if journal_path.is_file() {
+			fs::remove_file(journal_path)?;
+		}
+
+		Ok(())
+	}
+}
+
+fn get_journal_path(folder_path: &Path) -> PathBuf {
+	folder_path.join("install-journal.json")
+}
+
+// Most recent first, since later entries can depend on earlier ones.
+// Keeps going if undoing some entry fails, so we revert as much as possible.
+fn undo_entries(entries: &[JournalEntry]) {
+	for entry in entries.iter().rev() {
+		if let Err(err) = undo_entry(entry) {
+			error!(
+				"Failed to undo install journal entry for `{}`: {}",
+				entry.path.display(),
+				err
+			);
+		}
+	}
+}
+
+fn undo_entry(entry: &JournalEntry) -> Result {
+	match entry.kind {
+		JournalEntryKind::CreatedFile => {
+			if entry.path.is_file() {
+				fs::remove_file(&entry.path)?;
+			}
+		}
+		JournalEntryKind::ReplacedFile => {
+			if let Some(backup_path) = &entry.backup_path {
+				fs::copy(backup_path, &entry.path)?;
+				fs::remove_file(backup_path)?;
+			}
+		}
+		JournalEntryKind::Renamed => {
+			// If either side changed since the rename, moving it back could overwrite something.
+			if let Some(renamed_from) = &entry.renamed_from {
+				if entry.path.exists() && !renamed_from.exists() {
+					fs::rename(&entry.path, renamed_from)?;
+				}
+			}
+		}
+		JournalEntryKind::CreatedFolder => {
+			// Only removing the folder if it's empty,
+			// since the user might have put their own files in there.
+			if entry.path.is_dir() && fs::read_dir(&entry.path)?.next().is_none() {
+				fs::remove_dir(&entry.path)?;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use std::{
+		fs,
+		path::PathBuf,
+	};
+
+	use super::InstallJournal;
+
+	fn create_test_folder() -> PathBuf {
+		let folder = std::env::temp_dir().join(format!("rai-pal-test-{}", uuid::Uuid::new_v4()));
+		fs::create_dir_all(&folder).unwrap();
+		folder
+	}
+
+	#[test]
+	fn rollback_moves_renamed_file_back() {
+		let folder = create_test_folder();
+		let game_folder = folder.join("game");
+		let enabled_path = game_folder.join("mod.dll");
+		let disabled_path = game_folder.join("mod.dll.disabled");
+
+		let mut journal = InstallJournal::open(&folder.join("journal")).unwrap();
+		journal.write_file(&enabled_path, "mod").unwrap();
+
+		let checkpoint = journal.checkpoint();
+		journal.rename_file(&enabled_path, &disabled_path).unwrap();
+		assert!(!enabled_path.exists());
+		assert!(disabled_path.is_file());
+
+		journal.rollback(checkpoint).unwrap();
+		assert_eq!(fs::read_to_string(&enabled_path).unwrap(), "mod");
+		assert!(!disabled_path.exists());
+
+		fs::remove_dir_all(folder).unwrap();
+	}
+
+	#[test]
+	fn checkpoint_survives_removing_older_entries() {
+		let folder = create_test_folder();
+		let game_folder = folder.join("game");
+		fs::create_dir_all(&game_folder).unwrap();
+		let old_path = game_folder.join("old.dll");
+		let new_path = game_folder.join("new.dll");
+
+		let mut journal = InstallJournal::open(&folder.join("journal")).unwrap();
+		journal.write_file(&old_path, "old").unwrap();
+
+		let checkpoint = journal.checkpoint();
+		journal.remove_file(&old_path).unwrap();
+
+		// Checkpoints are meant to be shared between separate instances of the journal.
+		let mut other_journal = InstallJournal::open(&folder.join("journal")).unwrap();
+		other_journal.write_file(&new_path, "new").unwrap();
+
+		InstallJournal::open(&folder.join("journal"))
+			.unwrap()
+			.rollback(checkpoint)
+			.unwrap();
+		assert!(!old_path.exists());
+		assert!(!new_path.exists());
+
+		fs::remove_dir_all(folder).unwrap();
+	}
+
+	#[test]
+	fn restore_all_undoes_rename_over_existing_file() {
+		let folder = create_test_folder();
+		let game_folder = folder.join("game");
+		let source_path = game_folder.join("new.dll");
+		let target_path = game_folder.join("original.dll");
+		fs::create_dir_all(&game_folder).unwrap();
+		fs::write(&source_path, "new").unwrap();
+		fs::write(&target_path, "original").unwrap();
+
+		let mut journal = InstallJournal::open(&folder.join("journal")).unwrap();
+		journal.rename_file(&source_path, &target_path).unwrap();
+		assert_eq!(fs::read_to_string(&target_path).unwrap(), "new");
+
+		journal.restore_all().unwrap();
+		assert_eq!(fs::read_to_string(&source_path).unwrap(), "new");
+		assert_eq!(fs::read_to_string(&target_path).unwrap(), "original");
+
+		fs::remove_dir_all(folder).unwrap();
+	}
+
+	#[test]
+	fn remove_recorded_in_restores_replaced_files_and_keeps_user_files() {
+		let folder = create_test_folder();
+		let loader_folder = folder.join("game").join("Loader");
+		let replaced_path = loader_folder.join("config.ini");
+		let user_path = loader_folder.join("user.txt");
+		fs::create_dir_all(&loader_folder).unwrap();
+		fs::write(&replaced_path, "original").unwrap();
+
+		let mut journal = InstallJournal::open(&folder.join("journal")).unwrap();
+		journal.write_file(&replaced_path, "new").unwrap();
+		journal
+			.write_file(&loader_folder.join("mods").join("mod.dll"), "mod")
+			.unwrap();
+		fs::write(&user_path, "user").unwrap();
+
+		journal.remove_recorded_in(&loader_folder).unwrap();
+		assert!(journal.is_empty());
+		assert_eq!(fs::read_to_string(&replaced_path).unwrap(), "original");
+		assert_eq!(fs::read_to_string(&user_path).unwrap(), "user");
+		assert!(!loader_folder.join("mods").exists());
+
+		fs::remove_dir_all(folder).unwrap();
+	}
+
+	#[test]
+	fn remove_file_deletes_renamed_file() {
+		let folder = create_test_folder();
+		let game_folder = folder.join("game");
+		let enabled_path = game_folder.join("mod.dll");
+		let disabled_path = game_folder.join("mod.dll.disabled");
+
+		let mut journal = InstallJournal::open(&folder.join("journal")).unwrap();
+		journal.write_file(&enabled_path, "mod").unwrap();
+		journal.rename_file(&enabled_path, &disabled_path).unwrap();
+
+		journal.remove_file(&disabled_path).unwrap();
+		journal.remove_file(&enabled_path).unwrap();
+		assert!(!enabled_path.exists());
+		assert!(!disabled_path.exists());
+
+		journal.restore_all().unwrap();
+		assert!(!game_folder.exists());
+
+		fs::remove_dir_all(folder).unwrap();
+	}
+}
@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use crate::{
+	installed_game::InstalledGame,
+	local_mod,
+	remote_mod,
+	serializable_struct,
+	Error,
+	Result,
+};
+
+serializable_struct!(InstallStep {
+	pub mod_id: String,
+	pub loader_id: String,
+	// Mod that caused this one to be part of the plan. None for the mod the user asked for.
+	pub required_by: Option<String>,
+	pub needs_download: bool,
+	pub is_installed: bool,
+});
+
+serializable_struct!(ModConflict {
+	pub mod_id: String,
+	pub conflicting_mod_id: String,
+});
+
+// Steps are sorted so that each mod comes after all of its dependencies.
+serializable_struct!(InstallPlan {
+	pub steps: Vec<InstallStep>,
+	pub conflicts: Vec<ModConflict>,
+});
+
+struct Resolver<'a> {
+	game: &'a InstalledGame,
+	local_mods: &'a local_mod::Map,
+	remote_mods: &'a remote_mod::Map,
+	steps: Vec<InstallStep>,
+	// Chain of mods currently being resolved, used to detect circular dependencies.
+	visiting: Vec<String>,
+}
+
+impl<'a> Resolver<'a> {
+	// The database is the most up to date source, but local mods might not be in the database.
+	fn get_dependencies(&self, mod_id: &str) -> Vec<String> {
+		self.remote_mods.get(mod_id).map_or_else(
+			|| {
+				self.local_mods
+					.get(mod_id)
+					.and_then(|local_mod| local_mod.data.manifest.as_ref())
+					.map(|manifest| manifest.dependencies.clone())
+					.unwrap_or_default()
+			},
+			|remote_mod| remote_mod.data.dependencies.clone(),
+		)
+	}
+
+	fn get_conflicts(&self, mod_id: &str) -> Vec<String> {
+		self.remote_mods.get(mod_id).map_or_else(
+			|| {
+				self.local_mods
+					.get(mod_id)
+					.and_then(|local_mod| local_mod.data.manifest.as_ref())
+					.map(|manifest| manifest.conflicts.clone())
+					.unwrap_or_default()
+			},
+			|remote_mod| remote_mod.data.conflicts.clone(),
+		)
+	}
+
+	fn visit(&mut self, mod_id: &str) -> Result {
+		if self.steps.iter().any(|step| step.mod_id == mod_id) {
+			return Ok(());
+		}
+
+		let required_by = self.visiting.last().cloned();
+
+		if self
+			.visiting
+			.iter()
+			.any(|visiting_id| visiting_id == mod_id)
+		{
+			let mut chain = self.visiting.clone();
+			chain.push(mod_id.to_string());
+			return Err(Error::ModDependencyCycle(chain.join(" -> ")));
+		}
+
+		let local_mod = self.local_mods.get(mod_id);
+		let loader_id = local_mod
+			.map(|local_mod| &local_mod.common.loader_id)
+			.or_else(|| {
+				self.remote_mods
+					.get(mod_id)
+					.map(|remote_mod| &remote_mod.common.loader_id)
+			})
+			.ok_or_else(|| {
+				required_by.as_ref().map_or_else(
+					|| Error::DataEntryNotFound(mod_id.to_string()),
+					|required_by| {
+						Error::ModDependencyNotFound(mod_id.to_string(), required_by.clone())
+					},
+				)
+			})?
+			.clone();
+
+		self.visiting.push(mod_id.to_string());
+		for dependency_id in self.get_dependencies(mod_id) {
+			self.visit(&dependency_id)?;
+		}
+		self.visiting.pop();
+
+		self.steps.push(InstallStep {
+			mod_id: mod_id.to_string(),
+			loader_id,
+			required_by,
+			needs_download: local_mod.is_none(),
+			is_installed: self
+				.game
+				.installed_mod_versions
+				.get(mod_id)
+				.is_some_and(Option::is_some),
+		});
+
+		Ok(())
+	}
+
+	fn find_conflicts(&self) -> Vec<ModConflict> {
+		let planned_ids: HashSet<_> = self.steps.iter().map(|step| step.mod_id.as_str()).collect();
+
+		let installed_ids: HashSet<_> = self
+			.game
+			.installed_mod_versions
+			.iter()
+			.filter(|(_, version)| version.is_some())
+			.map(|(mod_id, _)| mod_id.as_str())
+			.collect();
+
+		let mut conflicts = Vec::new();
+
+		// Conflicts can be declared by either side, so we check both the mods we're about to
+		// install, and the mods that are already installed.
+		for mod_id in planned_ids.union(&installed_ids) {
+			let is_planned = planned_ids.contains(mod_id);
+
+			for conflicting_id in self.get_conflicts(mod_id) {
+				if conflicting_id != *mod_id
+					&& (planned_ids.contains(conflicting_id.as_str())
+						|| (is_planned && installed_ids.contains(conflicting_id.as_str())))
+				{
+					conflicts.push(ModConflict {
+						mod_id: (*mod_id).to_string(),
+						conflicting_mod_id: conflicting_id,
+					});
+				}
+			}
+		}
+
+		conflicts
+	}
+}
+
+pub fn resolve(
+	game: &InstalledGame,
+	mod_id: &str,
+	local_mods: &local_mod::Map,
+	remote_mods: &remote_mod::Map,
+) -> Result<InstallPlan> {
+	let mut resolver = Resolver {
+		game,
+		local_mods,
+		remote_mods,
+		steps: Vec::new(),
+		visiting: Vec::new(),
+	};
+
+	resolver.visit(mod_id)?;
+
+	Ok(InstallPlan {
+		conflicts: resolver.find_conflicts(),
+		steps: resolver.steps,
+	})
+}
@@ -1,5 +1,8 @@
 use std::{
-	collections::HashMap,
+	collections::{
+		HashMap,
+		HashSet,
+	},
 	fs::{
 		self,
 		File,
@@ -13,7 +16,12 @@ use std::{
 use crate::{
 	game_executable::GameExecutable,
 	game_mod,
+	install_journal::InstallJournal,
 	mod_manifest,
+	mod_profile::{
+		self,
+		ModProfiles,
+	},
 	owned_game,
 	paths::{
 		self,
@@ -24,6 +32,7 @@ use crate::{
 		provider_command::ProviderCommand,
 	},
 	serializable_struct,
+	steam::proton,
 	Error,
 	Result,
 };
@@ -34,10 +43,20 @@ serializable_struct!(InstalledGame {
 	pub provider: ProviderId,
 	pub executable: GameExecutable,
 	pub installed_mod_versions: InstalledModVersions,
+	pub pinned_mods: HashSet<String>,
+	pub disabled_mods: HashSet<String>,
+	pub disabled_mod_loaders: HashSet<String>,
+	// Profiles can be big, so only their names are kept here. The rest is read when needed.
+	pub mod_profile_names: Vec<String>,
+	pub active_mod_profile: Option<String>,
 	pub discriminator: Option<String>,
 	pub thumbnail_url: Option<String>,
 	pub owned_game_id: Option<String>,
+	// Id of the game in its provider, like the Steam app id.
+	pub provider_game_id: Option<String>,
 	pub start_command: Option<ProviderCommand>,
+	// Needed to find the Proton prefix, for games that run through Wine.
+	pub steam_app_id: Option<u32>,
 });
 
 pub type Map = HashMap<String, InstalledGame>;
@@ -45,8 +64,8 @@ type InstalledModVersions = HashMap<String, Option<String>>;
 
 impl InstalledGame {
 	pub fn new(path: &Path, name: &str, provider_id: ProviderId) -> Option<Self> {
-		// Games exported by Unity always have one of these extensions.
-		const VALID_EXTENSIONS: [&str; 3] = ["exe", "x86_64", "x86"];
+		// Games exported by Unity or Godot always have one of these extensions.
+		const VALID_EXTENSIONS: [&str; 4] = ["exe", "x86_64", "x86", "x86_32"];
 
 		if !path.is_file() {
 			return None;
@@ -73,11 +92,18 @@ impl InstalledGame {
 			name: name.to_string(),
 			provider: provider_id,
 			installed_mod_versions: HashMap::default(),
+			pinned_mods: HashSet::default(),
+			disabled_mods: HashSet::default(),
+			disabled_mod_loaders: HashSet::default(),
+			mod_profile_names: Vec::default(),
+			active_mod_profile: None,
 			executable: GameExecutable::new(path)?,
 			discriminator: None,
 			thumbnail_url: None,
 			start_command: None,
 			owned_game_id: None,
+			provider_game_id: None,
+			steam_app_id: None,
 		})
 	}
 
@@ -103,6 +129,12 @@ impl InstalledGame {
 
 	pub fn set_provider_game_id(&mut self, provider_game_id: &str) -> &Self {
 		self.owned_game_id = Some(owned_game::get_id(self.provider, provider_game_id));
+		self.provider_game_id = Some(provider_game_id.to_string());
+		self
+	}
+
+	pub const fn set_steam_app_id(&mut self, steam_app_id: u32) -> &Self {
+		self.steam_app_id = Some(steam_app_id);
 		self
 	}
 
@@ -118,6 +150,7 @@ impl InstalledGame {
 
 	pub fn update_available_mods(&mut self, data_map: &game_mod::CommonDataMap) {
 		self.installed_mod_versions = self.get_available_mods(data_map);
+		self.refresh_mod_states();
 	}
 
 	pub fn open_game_folder(&self) -> Result {
@@ -140,21 +173,7 @@ impl InstalledGame {
 		Ok(open::that_detached(&self.executable.path)?)
 	}
 
-	pub fn uninstall_mod(&self, mod_id: &str) -> Result {
-		// TODO this should be handled by each mod loader.
-		let installed_mods_folder = self.get_installed_mods_folder()?;
-		let bepinex_folder = installed_mods_folder.join("BepInEx");
-
-		let plugins_folder = bepinex_folder.join("plugins").join(mod_id);
-		if plugins_folder.is_dir() {
-			fs::remove_dir_all(plugins_folder)?;
-		}
-
-		let patchers_folder = bepinex_folder.join("patchers").join(mod_id);
-		if patchers_folder.is_dir() {
-			fs::remove_dir_all(patchers_folder)?;
-		}
-
+	pub fn remove_installed_mod_manifest(&self, mod_id: &str) -> Result {
 		let manifest_path = self.get_installed_mod_manifest_path(mod_id)?;
 		if manifest_path.is_file() {
 			fs::remove_file(manifest_path)?;
@@ -165,6 +184,17 @@ impl InstalledGame {
 
 	pub fn refresh_mods(&mut self, data_map: &game_mod::CommonDataMap) {
 		self.installed_mod_versions = self.get_available_mods(data_map);
+		self.refresh_mod_states();
+	}
+
+	fn refresh_mod_states(&mut self) {
+		self.pinned_mods = self.get_installed_mods_where(|manifest| manifest.pinned);
+		self.disabled_mods = self.get_installed_mods_where(|manifest| manifest.disabled);
+		self.disabled_mod_loaders = self.get_disabled_mod_loaders();
+		let mod_profiles = mod_profile::read(self);
+		self.mod_profile_names = mod_profiles.profiles.into_keys().collect();
+		self.mod_profile_names.sort();
+		self.active_mod_profile = mod_profiles.active;
 	}
 
 	pub fn get_installed_mods_folder(&self) -> Result<PathBuf> {
@@ -176,6 +206,41 @@ impl InstalledGame {
 		Ok(installed_mods_folder)
 	}
 
+	pub fn open_install_journal(&self) -> Result<InstallJournal> {
+		InstallJournal::open(&self.get_installed_mods_folder()?)
+	}
+
+	// Undoes every change Rai Pal made to the game files, and deletes all installed mods.
+	// Mod profiles are kept, so the user can go back to any of them later.
+	pub fn restore_vanilla(&self) -> Result {
+		self.open_install_journal()?.restore_all()?;
+		proton::remove_dll_overrides(self)?;
+
+		self.remove_installed_mods_folder()
+	}
+
+	// Deletes everything Rai Pal keeps for this game, except for the mod profiles.
+	pub fn remove_installed_mods_folder(&self) -> Result {
+		let mod_profiles = mod_profile::read(self);
+
+		let installed_mods_folder = self.get_installed_mods_folder()?;
+		if installed_mods_folder.is_dir() {
+			fs::remove_dir_all(installed_mods_folder)?;
+		}
+
+		if !mod_profiles.profiles.is_empty() {
+			mod_profile::write(
+				self,
+				&ModProfiles {
+					active: None,
+					..mod_profiles
+				},
+			)?;
+		}
+
+		Ok(())
+	}
+
 	pub fn get_installed_mod_manifest_path(&self, mod_id: &str) -> Result<PathBuf> {
 		Ok(self
 			.get_installed_mods_folder()?
@@ -183,11 +248,156 @@ impl InstalledGame {
 			.join(format!("{mod_id}.json")))
 	}
 
-	pub fn get_installed_mod_version(&self, mod_id: &str) -> Option<String> {
+	// Lists what's actually installed, which can be newer than `installed_mod_versions`.
+	pub fn get_installed_mod_manifests(&self) -> Result<HashMap<String, mod_manifest::Manifest>> {
+		let manifests_path = self.get_installed_mods_folder()?.join("manifests");
+		if !manifests_path.is_dir() {
+			return Ok(HashMap::new());
+		}
+
+		let mut manifests = HashMap::new();
+		for entry in fs::read_dir(manifests_path)? {
+			let path = entry?.path();
+			if !path
+				.extension()
+				.is_some_and(|extension| extension == "json")
+			{
+				continue;
+			}
+
+			if let (Some(mod_id), Some(manifest)) = (
+				path.file_stem().and_then(|stem| stem.to_str()),
+				mod_manifest::get(&path),
+			) {
+				manifests.insert(mod_id.to_string(), manifest);
+			}
+		}
+
+		Ok(manifests)
+	}
+
+	pub fn has_installed_mod_manifests(&self) -> Result<bool> {
+		let manifests_path = self.get_installed_mods_folder()?.join("manifests");
+
+		Ok(manifests_path.is_dir() && fs::read_dir(manifests_path)?.next().is_some())
+	}
+
+	pub fn get_installed_mod_manifest(&self, mod_id: &str) -> Option<mod_manifest::Manifest> {
 		let manifest_path = self.get_installed_mod_manifest_path(mod_id).ok()?;
 		let manifest_file = File::open(manifest_path).ok()?;
-		let manifest: mod_manifest::Manifest = serde_json::from_reader(manifest_file).ok()?;
-		Some(manifest.version)
+		serde_json::from_reader(manifest_file).ok()
+	}
+
+	// Manifests from before the loader id was saved in them only have the loader in the installed files lists.
+	pub fn get_installed_mod_loader_id(&self, mod_id: &str) -> Option<String> {
+		if let Some(loader_id) = self.get_installed_mod_manifest(mod_id)?.loader_id {
+			return Some(loader_id);
+		}
+
+		let installed_files_path = self
+			.get_installed_mods_folder()
+			.ok()?
+			.join("installed-files");
+		fs::read_dir(installed_files_path)
+			.ok()?
+			.filter_map(std::result::Result::ok)
+			.find(|entry| entry.path().join(format!("{mod_id}.json")).is_file())
+			.and_then(|entry| entry.file_name().to_str().map(ToString::to_string))
+	}
+
+	// Mods that aren't in the local mods or any database anymore still know which loader installed them.
+	pub fn get_mod_loader_id(
+		&self,
+		mod_id: &str,
+		data_map: &game_mod::CommonDataMap,
+	) -> Result<String> {
+		data_map.get(mod_id).map_or_else(
+			|| {
+				self.get_installed_mod_loader_id(mod_id)
+					.ok_or_else(|| Error::DataEntryNotFound(mod_id.to_string()))
+			},
+			|mod_data| Ok(mod_data.loader_id.clone()),
+		)
+	}
+
+	pub fn get_installed_mod_version(&self, mod_id: &str) -> Option<String> {
+		Some(self.get_installed_mod_manifest(mod_id)?.version)
+	}
+
+	fn update_installed_mod_manifest<F>(&self, mod_id: &str, update: F) -> Result
+	where
+		F: FnOnce(&mut mod_manifest::Manifest),
+	{
+		let mut manifest = self
+			.get_installed_mod_manifest(mod_id)
+			.ok_or_else(|| Error::ModNotInstalled(mod_id.to_string()))?;
+
+		update(&mut manifest);
+
+		fs::write(
+			self.get_installed_mod_manifest_path(mod_id)?,
+			serde_json::to_string_pretty(&manifest)?,
+		)?;
+
+		Ok(())
+	}
+
+	pub fn set_mod_pinned(&self, mod_id: &str, pinned: bool) -> Result {
+		self.update_installed_mod_manifest(mod_id, |manifest| manifest.pinned = pinned)
+	}
+
+	pub fn set_mod_disabled(&self, mod_id: &str, disabled: bool) -> Result {
+		self.update_installed_mod_manifest(mod_id, |manifest| manifest.disabled = disabled)
+	}
+
+	fn get_installed_mods_where<F>(&self, predicate: F) -> HashSet<String>
+	where
+		F: Fn(&mod_manifest::Manifest) -> bool,
+	{
+		self.installed_mod_versions
+			.keys()
+			.filter(|mod_id| {
+				self.get_installed_mod_manifest(mod_id)
+					.is_some_and(|manifest| predicate(&manifest))
+			})
+			.cloned()
+			.collect()
+	}
+
+	fn get_disabled_mod_loaders_path(&self) -> Result<PathBuf> {
+		Ok(self
+			.get_installed_mods_folder()?
+			.join("disabled-mod-loaders.json"))
+	}
+
+	// Reads from disk instead of using the cached field, since loaders need the current state.
+	pub fn get_disabled_mod_loaders(&self) -> HashSet<String> {
+		self.get_disabled_mod_loaders_path()
+			.ok()
+			.and_then(|path| fs::read_to_string(path).ok())
+			.and_then(|contents| serde_json::from_str(&contents).ok())
+			.unwrap_or_default()
+	}
+
+	pub fn is_mod_loader_disabled(&self, mod_loader_id: &str) -> bool {
+		self.get_disabled_mod_loaders().contains(mod_loader_id)
+	}
+
+	pub fn set_mod_loader_disabled(&self, mod_loader_id: &str, disabled: bool) -> Result {
+		let mut disabled_mod_loaders = self.get_disabled_mod_loaders();
+
+		if disabled {
+			disabled_mod_loaders.insert(mod_loader_id.to_string());
+		} else {
+			disabled_mod_loaders.remove(mod_loader_id);
+		}
+
+		fs::write(
+			self.get_disabled_mod_loaders_path()?,
+			serde_json::to_string_pretty(&disabled_mod_loaders)?,
+		)?;
+
+		Ok(())
 	}
 
 	pub fn get_available_mods(&self, data_map: &game_mod::CommonDataMap) -> InstalledModVersions {
@@ -7,6 +7,7 @@ use std::{
 };
 
 use crate::{
+	dotnet_assembly,
 	game_engines::{
 		game_engine::GameEngineBrand,
 		unity::UnityScriptingBackend,
@@ -30,6 +31,8 @@ serializable_enum!(ModKind {
 serializable_struct!(LocalModData {
 	pub path: PathBuf,
 	pub manifest: Option<Manifest>,
+	// Read from the mod's assemblies, since the folder the mod is in can be wrong.
+	pub detected_unity_backend: Option<UnityScriptingBackend>,
 });
 
 serializable_struct!(LocalMod {
@@ -41,6 +44,22 @@ pub fn get_manifest_path(mod_path: &Path) -> PathBuf {
 	mod_path.join("rai-pal-manifest.json")
 }
 
+impl LocalModData {
+	fn new(path: &Path, engine: Option<GameEngineBrand>) -> Self {
+		Self {
+			path: path.to_path_buf(),
+			manifest: mod_manifest::get(&get_manifest_path(path)),
+			detected_unity_backend: if engine == Some(GameEngineBrand::Unity) && path.is_dir() {
+				dotnet_assembly::get_unity_backend(
+					&dotnet_assembly::get_folder_assembly_references(path),
+				)
+			} else {
+				None
+			},
+		}
+	}
+}
+
 impl LocalMod {
 	pub fn new(
 		loader_id: &str,
@@ -49,10 +68,7 @@ impl LocalMod {
 		unity_backend: Option<UnityScriptingBackend>,
 	) -> Result<Self> {
 		Ok(Self {
-			data: LocalModData {
-				path: path.to_path_buf(),
-				manifest: mod_manifest::get(&get_manifest_path(path)),
-			},
+			data: LocalModData::new(path, engine),
 			common: CommonModData {
 				id: paths::file_name_without_extension(path)?.to_string(),
 				engine,
@@ -62,6 +78,13 @@ impl LocalMod {
 		})
 	}
 
+	pub fn from_common_data(common: &CommonModData, path: &Path) -> Self {
+		Self {
+			data: LocalModData::new(path, common.engine),
+			common: common.clone(),
+		}
+	}
+
 	pub fn open_folder(&self) -> Result {
 		let path = if self.data.path.is_dir() {
 			&self.data.path
@@ -4,9 +4,16 @@
 
 use std::{
 	collections::HashMap,
-	path::PathBuf,
+	env,
+	path::{
+		Path,
+		PathBuf,
+	},
 	sync::Mutex,
-	time::Instant,
+	time::{
+		Duration,
+		Instant,
+	},
 };
 
 use app_state::{
@@ -15,6 +22,10 @@ use app_state::{
 	StateData,
 	StatefulHandle,
 };
+use database_sources::{
+	DatabaseSources,
+	ModDatabaseSource,
+};
 use debug::LoggableInstant;
 use events::{
 	AppEvent,
@@ -22,16 +33,30 @@ use events::{
 };
 use game_mod::get_common_data_map;
 use installed_game::InstalledGame;
+use local_mod::{
+	LocalMod,
+	ModKind,
+};
 use log::error;
 use maps::TryGettable;
-use mod_loaders::mod_loader::{
-	self,
-	ModLoaderActions,
+use mod_loaders::{
+	bepinex,
+	bepinex_config,
+	mod_database,
+	mod_loader::{
+		self,
+		ModLoader,
+		ModLoaderActions,
+	},
 };
 use paths::{
 	hash_path,
 	normalize_path,
 };
+use progress::{
+	ModProgress,
+	ProgressPhase,
+};
 use providers::{
 	manual_provider,
 	provider::{
@@ -43,6 +68,11 @@ use result::{
 	Error,
 	Result,
 };
+use runnable_process::{
+	RunnableExit,
+	RunnableProcesses,
+	RunningRunnable,
+};
 use steamlocate::SteamDir;
 use tauri::{
 	AppHandle,
@@ -52,28 +82,44 @@ use tauri_plugin_log::LogTarget;
 
 mod analytics;
 mod app_state;
+mod database_sources;
 mod debug;
+mod dotnet_assembly;
+mod downloads;
 mod events;
 mod files;
 mod game_engines;
 mod game_executable;
 mod game_mod;
 mod game_mode;
+mod game_process;
+mod install_journal;
+mod install_plan;
 mod installed_game;
 mod local_mod;
 mod macros;
 mod maps;
+mod mod_import;
 mod mod_loaders;
 mod mod_manifest;
+mod mod_profile;
+mod mod_updates;
 mod operating_systems;
 mod owned_game;
 mod paths;
 mod pc_gaming_wiki;
+mod progress;
 mod providers;
 mod remote_mod;
 mod result;
+mod runnable_process;
+mod signatures;
 mod steam;
 mod windows;
+mod wine_registry;
+
+// How long to wait for the game process to show up before giving up on running a mod that needs it.
+const GAME_PROCESS_TIMEOUT: Duration = Duration::from_secs(120);
 
 #[tauri::command]
 #[specta::specta]
@@ -172,7 +218,9 @@ async fn download_mod(mod_id: &str, handle: AppHandle) -> Result {
 
 	mod_loaders
 		.try_get(&remote_mod.common.loader_id)?
-		.download_mod(&remote_mod)
+		.download_mod(&remote_mod, None, |progress| {
+			handle.emit_event(AppEvent::ModProgress, progress);
+		})
 		.await?;
 
 	refresh_local_mods(&mod_loaders, &handle).await;
@@ -206,12 +254,90 @@ async fn start_game_exe(game_id: &str, handle: AppHandle) -> Result {
 
 #[tauri::command]
 #[specta::specta]
-async fn install_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
+async fn get_install_plan(
+	game_id: &str,
+	mod_id: &str,
+	handle: AppHandle,
+) -> Result<install_plan::InstallPlan> {
 	let state = handle.app_state();
 
-	let mut installed_games = state.installed_games.get_data()?;
-	let game = installed_games.try_get_mut(game_id)?;
-	let mod_loaders = state.mod_loaders.get_data()?;
+	install_plan::resolve(
+		&state.installed_games.try_get(game_id)?,
+		mod_id,
+		&state.local_mods.get_data()?,
+		&state.remote_mods.get_data()?,
+	)
+}
+
+// `approved_mod_ids` are the dependencies the user agreed to install after seeing the install plan.
+#[tauri::command]
+#[specta::specta]
+async fn install_mod(
+	game_id: &str,
+	mod_id: &str,
+	approved_mod_ids: Vec<String>,
+	handle: AppHandle,
+) -> Result {
+	let state = handle.app_state();
+
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+
+	let plan = install_plan::resolve(
+		game,
+		mod_id,
+		&state.local_mods.get_data()?,
+		&state.remote_mods.get_data()?,
+	)?;
+
+	if let Some(conflict) = plan.conflicts.first() {
+		return Err(Error::ModConflict(
+			conflict.mod_id.clone(),
+			conflict.conflicting_mod_id.clone(),
+		));
+	}
+
+	// Dependencies that are already installed are left alone,
+	// but the mod the user asked for always gets (re)installed.
+	let steps_to_install: Vec<_> = plan
+		.steps
+		.iter()
+		.filter(|step| !step.is_installed || step.required_by.is_none())
+		.collect();
+
+	// The plan could have changed since the user saw it, so nothing extra gets installed without approval.
+	if let Some(step) = steps_to_install
+		.iter()
+		.find(|step| step.required_by.is_some() && !approved_mod_ids.contains(&step.mod_id))
+	{
+		return Err(Error::ModDependencyNotApproved(
+			step.mod_id.clone(),
+			mod_id.to_string(),
+		));
+	}
+
+	for step in steps_to_install {
+		install_single_mod(game, &step.mod_id, &handle).await?;
+	}
+
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	analytics::send_event(analytics::Event::InstallOrRunMod, mod_id).await;
+
+	Ok(())
+}
+
+// Finds the local mod, downloading it first if needed.
+async fn get_or_download_local_mod(
+	mod_id: &str,
+	mod_loaders: &mod_loader::Map,
+	handle: &AppHandle,
+) -> Result<LocalMod> {
+	let state = handle.app_state();
+
+	let on_progress = |progress: ModProgress| {
+		handle.emit_event(AppEvent::ModProgress, progress);
+	};
 
 	let local_mods = {
 		let state_local_mods = state.local_mods.get_data()?;
@@ -220,7 +346,7 @@ async fn install_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 		} else {
 			// Local mod wasn't in app state,
 			// so let's sync app state to local files in case some file was manually changed.
-			let disk_local_mods = refresh_local_mods(&mod_loaders, &handle).await;
+			let disk_local_mods = refresh_local_mods(mod_loaders, handle).await;
 
 			if state_local_mods.contains_key(mod_id) {
 				disk_local_mods
@@ -232,7 +358,7 @@ async fn install_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 					// If local mod still can't be found on disk,
 					// we try to download it from the database.
 					mod_loader
-						.download_mod(&state.remote_mods.try_get(mod_id)?)
+						.download_mod(&state.remote_mods.try_get(mod_id)?, None, on_progress)
 						.await?;
 				} else {
 					// If downloading from the database isn't possible,
@@ -240,19 +366,190 @@ async fn install_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 					mod_loader.open_folder()?;
 				}
 
-				refresh_local_mods(&mod_loaders, &handle).await
+				refresh_local_mods(mod_loaders, handle).await
 			}
 		}
 	};
 
-	let local_mod = local_mods.try_get(mod_id)?;
+	Ok(local_mods.try_get(mod_id)?.clone())
+}
+
+async fn install_single_mod(game: &InstalledGame, mod_id: &str, handle: &AppHandle) -> Result {
+	let mod_loaders = handle.app_state().mod_loaders.get_data()?;
+	let local_mod = &get_or_download_local_mod(mod_id, &mod_loaders, handle).await?;
 
 	let mod_loader = mod_loaders.try_get(&local_mod.common.loader_id)?;
 
-	mod_loader.install_mod(game, local_mod).await?;
+	// Clean up whatever version was there before, so we don't end up with a mix of files from different versions.
+	if game.get_installed_mod_version(mod_id).is_some() {
+		mod_loader.uninstall_mod(game, mod_id)?;
+	}
+
+	install_local_mod(mod_loader, game, local_mod, handle).await
+}
+
+// Injectors need the game to be running already, so this starts the game,
+// waits for its process to show up, and only then starts the runnable with the game's process id.
+#[tauri::command]
+#[specta::specta]
+async fn start_game_and_run_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
+	let game = handle.app_state().installed_games.try_get(game_id)?;
+	let mod_loaders = handle.app_state().mod_loaders.get_data()?;
+	let local_mod = get_or_download_local_mod(mod_id, &mod_loaders, &handle).await?;
+
+	if mod_loaders
+		.try_get(&local_mod.common.loader_id)?
+		.get_data()
+		.kind != ModKind::Runnable
+	{
+		return Err(Error::RunnableManifestNotFound(mod_id.to_string()));
+	}
+
+	// No need to start the game again if it's already running.
+	let game_process_id = if let Some(process_id) =
+		game_process::find_process_id(&game.executable.path)
+	{
+		process_id
+	} else {
+		game.start()?;
+		handle.emit_event(AppEvent::ExecutedProviderCommand, ());
+
+		let executable_path = game.executable.path.clone();
+		let progress_mod_id = mod_id.to_string();
+		let progress_handle = handle.clone();
+
+		tauri::async_runtime::spawn_blocking(move || {
+			game_process::wait_for_process_id(&executable_path, GAME_PROCESS_TIMEOUT, |elapsed| {
+				progress_handle.emit_event(
+					AppEvent::ModProgress,
+					ModProgress::new(
+						&progress_mod_id,
+						ProgressPhase::WaitForGame,
+						elapsed.as_secs(),
+						Some(GAME_PROCESS_TIMEOUT.as_secs()),
+					),
+				);
+			})
+		})
+		.await??
+	};
+
+	runnable_process::start(&handle, &game, &local_mod, Some(game_process_id))?;
+	handle.emit_event(AppEvent::ModProgress, ModProgress::finished(mod_id));
+
+	analytics::send_event(analytics::Event::InstallOrRunMod, mod_id).await;
+
+	Ok(())
+}
+
+// Runnables get started here instead of by their mod loader, so the process can be tracked.
+async fn install_local_mod(
+	mod_loader: &ModLoader,
+	game: &InstalledGame,
+	local_mod: &LocalMod,
+	handle: &AppHandle,
+) -> Result {
+	if mod_loader.get_data().kind == ModKind::Runnable {
+		runnable_process::start(handle, game, local_mod, None)?;
+		handle.emit_event(
+			AppEvent::ModProgress,
+			ModProgress::finished(&local_mod.common.id),
+		);
+		return Ok(());
+	}
+
+	mod_loader
+		.install_mod(game, local_mod, |progress: ModProgress| {
+			handle.emit_event(AppEvent::ModProgress, progress);
+		})
+		.await
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn install_mod_version(
+	game_id: &str,
+	mod_id: &str,
+	version_id: &str,
+	handle: AppHandle,
+) -> Result {
+	let state = handle.app_state();
+
+	let mut installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get_mut(game_id)?;
+	let mod_loaders = state.mod_loaders.get_data()?;
+	let remote_mod = state.remote_mods.try_get(mod_id)?;
+	let mod_loader = mod_loaders.try_get(&remote_mod.common.loader_id)?;
+
+	let on_progress = |progress: ModProgress| {
+		handle.emit_event(AppEvent::ModProgress, progress);
+	};
+
+	let local_mod =
+		if let Ok(local_mod) = mod_loader.get_local_mod_version(&remote_mod.common, version_id) {
+			local_mod
+		} else {
+			mod_loader
+				.download_mod(&remote_mod, Some(version_id), on_progress)
+				.await?;
+
+			refresh_local_mods(&mod_loaders, &handle).await;
+
+			mod_loader.get_local_mod_version(&remote_mod.common, version_id)?
+		};
+
+	if game.get_installed_mod_version(mod_id).is_some() {
+		mod_loader.uninstall_mod(game, mod_id)?;
+	}
+
+	install_local_mod(mod_loader, game, &local_mod, &handle).await?;
+
+	// Pinned mods are left alone when the mod gets updated,
+	// until the user installs the latest version again.
+	if mod_loader.get_data().kind != ModKind::Runnable {
+		game.set_mod_pinned(mod_id, true)?;
+	}
+
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	analytics::send_event(analytics::Event::InstallOrRunMod, mod_id).await;
+
+	Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_mod_updates(handle: AppHandle) -> Result<mod_updates::Map> {
+	let state = handle.app_state();
+
+	Ok(mod_updates::get_map(
+		&state.installed_games.get_data()?,
+		&state.remote_mods.get_data()?,
+	))
+}
 
+#[tauri::command]
+#[specta::specta]
+async fn update_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+
+	let mut installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get_mut(game_id)?;
+	let mod_loaders = state.mod_loaders.get_data()?;
+	let remote_mod = state.remote_mods.try_get(mod_id)?;
+	let mod_loader = mod_loaders.try_get(&remote_mod.common.loader_id)?;
+
+	let update_result = mod_updates::update_mod(game, mod_loader, &remote_mod, |progress| {
+		handle.emit_event(AppEvent::ModProgress, progress);
+	})
+	.await;
+
+	// Even if the update failed, the local files might have changed.
+	refresh_local_mods(&mod_loaders, &handle).await;
 	refresh_game_mods_and_exe(&game.id, &handle)?;
 
+	update_result?;
+
 	analytics::send_event(analytics::Event::InstallOrRunMod, mod_id).await;
 
 	Ok(())
@@ -293,9 +590,211 @@ async fn refresh_game(game_id: &str, handle: AppHandle) -> Result {
 #[specta::specta]
 async fn uninstall_mod(game_id: &str, mod_id: &str, handle: AppHandle) -> Result {
 	let state = handle.app_state();
-	let mut installed_games = state.installed_games.get_data()?;
-	let game = installed_games.try_get_mut(game_id)?;
-	game.uninstall_mod(mod_id)?;
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+	let mod_data_map = get_common_data_map(
+		&state.local_mods.get_data()?,
+		&state.remote_mods.get_data()?,
+	);
+	let mod_loaders = state.mod_loaders.get_data()?;
+
+	mod_loaders
+		.try_get(&game.get_mod_loader_id(mod_id, &mod_data_map)?)?
+		.uninstall_mod(game, mod_id)?;
+
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn uninstall_mod_loader(game_id: &str, mod_loader_id: &str, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+	let mod_data_map = get_common_data_map(
+		&state.local_mods.get_data()?,
+		&state.remote_mods.get_data()?,
+	);
+	let mod_loader = state.mod_loaders.try_get(mod_loader_id)?;
+
+	// Mods can't work without their loader, so they get removed too.
+	for (mod_id, installed_version) in &game.installed_mod_versions {
+		if installed_version.is_some()
+			&& game
+				.get_mod_loader_id(mod_id, &mod_data_map)
+				.is_ok_and(|loader_id| loader_id == mod_loader_id)
+		{
+			mod_loader.uninstall_mod(game, mod_id)?;
+		}
+	}
+
+	mod_loader.uninstall(game)?;
+
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn set_mod_enabled(game_id: &str, mod_id: &str, enabled: bool, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+	let mod_data_map = get_common_data_map(
+		&state.local_mods.get_data()?,
+		&state.remote_mods.get_data()?,
+	);
+
+	state
+		.mod_loaders
+		.try_get(&mod_data_map.try_get(mod_id)?.loader_id)?
+		.set_mod_enabled(game, mod_id, enabled)?;
+
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn set_mod_loader_enabled(
+	game_id: &str,
+	mod_loader_id: &str,
+	enabled: bool,
+	handle: AppHandle,
+) -> Result {
+	let state = handle.app_state();
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+
+	state
+		.mod_loaders
+		.try_get(mod_loader_id)?
+		.set_enabled(game, enabled)?;
+
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_bepinex_config_files(game_id: &str, handle: AppHandle) -> Result<Vec<String>> {
+	bepinex_config::get_file_names(&handle.app_state().installed_games.try_get(game_id)?)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_bepinex_config(
+	game_id: &str,
+	file_name: &str,
+	handle: AppHandle,
+) -> Result<bepinex_config::BepInExConfig> {
+	bepinex_config::read(
+		&handle.app_state().installed_games.try_get(game_id)?,
+		file_name,
+	)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn set_bepinex_config_value(
+	game_id: &str,
+	file_name: &str,
+	section: &str,
+	key: &str,
+	value: &str,
+	handle: AppHandle,
+) -> Result {
+	bepinex_config::write_value(
+		&handle.app_state().installed_games.try_get(game_id)?,
+		file_name,
+		section,
+		key,
+		value,
+	)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_bepinex_launch_options(game_id: &str, handle: AppHandle) -> Result<String> {
+	bepinex::get_steam_launch_options(&handle.app_state().installed_games.try_get(game_id)?)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_mod_profiles(game_id: &str, handle: AppHandle) -> Result<mod_profile::ModProfiles> {
+	Ok(mod_profile::read(
+		&handle.app_state().installed_games.try_get(game_id)?,
+	))
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn save_mod_profile(game_id: &str, profile_name: &str, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+
+	mod_profile::save(game, profile_name, &state.mod_loaders.get_data()?)?;
+
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn delete_mod_profile(game_id: &str, profile_name: &str, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+
+	mod_profile::delete(game, profile_name)?;
+
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn switch_mod_profile(game_id: &str, profile_name: &str, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+	let mod_loaders = state.mod_loaders.get_data()?;
+	let remote_mods = state.remote_mods.get_data()?;
+
+	let switch_result = mod_profile::switch(
+		game,
+		profile_name,
+		&mod_loaders,
+		&get_common_data_map(&state.local_mods.get_data()?, &remote_mods),
+		&remote_mods,
+		|progress| {
+			handle.emit_event(AppEvent::ModProgress, progress);
+		},
+	)
+	.await;
+
+	// Even if switching failed, some mods might have been downloaded or changed.
+	refresh_local_mods(&mod_loaders, &handle).await;
+	refresh_game_mods_and_exe(&game.id, &handle)?;
+
+	switch_result
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn restore_game_to_vanilla(game_id: &str, handle: AppHandle) -> Result {
+	let state = handle.app_state();
+	let installed_games = state.installed_games.get_data()?;
+	let game = installed_games.try_get(game_id)?;
+	game.restore_vanilla()?;
 
 	refresh_game_mods_and_exe(&game.id, &handle)?;
 
@@ -321,12 +820,27 @@ async fn refresh_local_mods(mod_loaders: &mod_loader::Map, handle: &AppHandle) -
 	local_mods
 }
 
+// Broken custom sources fall back to just the official database, instead of showing no mods at all.
+fn get_mod_database_sources(handle: &AppHandle) -> Result<Vec<ModDatabaseSource>> {
+	let sources = database_sources::read().unwrap_or_else(|error| {
+		handle.emit_error(format!("Failed to read mod database sources: {error}"));
+		DatabaseSources::official_only()
+	});
+
+	sources.get_mod_databases(&paths::resources_path(handle)?)
+}
+
 async fn refresh_remote_mods(mod_loaders: &mod_loader::Map, handle: &AppHandle) -> remote_mod::Map {
 	let mut remote_mods = remote_mod::Map::default();
 
+	let sources = get_mod_database_sources(handle).unwrap_or_else(|error| {
+		handle.emit_error(format!("Failed to get mod database sources: {error}"));
+		Vec::new()
+	});
+
 	for mod_loader in mod_loaders.values() {
 		for (mod_id, remote_mod) in mod_loader
-			.get_remote_mods(|error| {
+			.get_remote_mods(&sources, |error| {
 				handle.emit_error(format!("Failed to get remote mods: {error}"));
 			})
 			.await
@@ -439,6 +953,16 @@ async fn update_data(handle: AppHandle) -> Result {
 	Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+async fn import_mod(path: PathBuf, handle: AppHandle) -> Result<String> {
+	let mod_id = mod_import::import_mod(&path)?;
+
+	refresh_local_mods(&handle.app_state().mod_loaders.get_data()?, &handle).await;
+
+	Ok(mod_id)
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn add_game(path: PathBuf, handle: AppHandle) -> Result {
@@ -559,6 +1083,30 @@ async fn frontend_ready() -> Result {
 	Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+async fn get_running_runnables(handle: AppHandle) -> Result<Vec<RunningRunnable>> {
+	handle.app_state().runnable_processes.get_running()
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn stop_runnable(pid: u32, handle: AppHandle) -> Result {
+	handle.app_state().runnable_processes.stop(pid)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_database_sources() -> Result<DatabaseSources> {
+	database_sources::read()
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn set_database_sources(sources: DatabaseSources) -> Result {
+	database_sources::write(&sources)
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn open_logs_folder() -> Result {
@@ -569,13 +1117,56 @@ async fn open_logs_folder() -> Result {
 
 #[tauri::command]
 #[specta::specta]
-async fn dummy_command() -> Result<(InstalledGame, AppEvent)> {
+async fn dummy_command() -> Result<(InstalledGame, AppEvent, ModProgress, RunnableExit)> {
 	// This command is here just so tauri_specta exports these types.
 	// This should stop being needed once tauri_specta starts supporting events.
 	Err(Error::NotImplemented)
 }
 
+fn validate_mod_database(database_path: Option<String>) -> i32 {
+	let Some(database_path) = database_path else {
+		eprintln!("Usage: rai-pal --validate-mod-database <path to database json>");
+		return 2;
+	};
+
+	match mod_database::validate_file(Path::new(&database_path)) {
+		Ok(issues) if issues.is_empty() => {
+			println!("No issues found in {database_path}");
+			0
+		}
+		Ok(issues) => {
+			for issue in &issues {
+				println!("{issue}");
+			}
+			println!("Found {} issues in {database_path}", issues.len());
+			1
+		}
+		Err(error) => {
+			eprintln!("Failed to read mod database {database_path}: {error}");
+			1
+		}
+	}
+}
+
+// Lets database maintainers check a database file before publishing it:
+// `rai-pal --validate-mod-database path/to/database.json`
+// Exits without starting the app if it was started as a command line tool.
+fn run_command_line() {
+	let mut args = env::args().skip(1);
+
+	if args.next().as_deref() != Some("--validate-mod-database") {
+		return;
+	}
+
+	windows::attach_parent_console();
+	std::process::exit(validate_mod_database(args.next()));
+}
+
+// Most of this is the list of commands exposed to the frontend.
+#[allow(clippy::too_many_lines)]
 fn main() {
+	run_command_line();
+
 	// Since I'm making all exposed functions async, panics won't crash anything important, I think.
 	// So I can just catch panics here and show a system message with the error.
 	std::panic::set_hook(Box::new(|info| {
@@ -599,6 +1190,7 @@ fn main() {
 			mod_loaders: Mutex::default(),
 			local_mods: Mutex::default(),
 			remote_mods: Mutex::default(),
+			runnable_processes: RunnableProcesses::default(),
 		})
 		.setup(|app| {
 			// This prevents/reduces the white flashbang on app start.
@@ -632,8 +1224,29 @@ fn main() {
 			get_owned_games,
 			get_mod_loaders,
 			open_game_folder,
+			get_install_plan,
 			install_mod,
+			install_mod_version,
+			update_mod,
+			get_mod_updates,
 			uninstall_mod,
+			uninstall_mod_loader,
+			set_mod_enabled,
+			set_mod_loader_enabled,
+			get_bepinex_config_files,
+			get_bepinex_config,
+			set_bepinex_config_value,
+			get_bepinex_launch_options,
+			get_running_runnables,
+			stop_runnable,
+			get_database_sources,
+			set_database_sources,
+			start_game_and_run_mod,
+			get_mod_profiles,
+			save_mod_profile,
+			delete_mod_profile,
+			switch_mod_profile,
+			restore_game_to_vanilla,
 			open_game_mods_folder,
 			start_game,
 			start_game_exe,
@@ -641,6 +1254,7 @@ fn main() {
 			download_mod,
 			open_mods_folder,
 			add_game,
+			import_mod,
 			remove_game,
 			delete_steam_appinfo_cache,
 			frontend_ready,
@@ -0,0 +1,285 @@
+use std::{
+	collections::HashSet,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use crate::{
+	dotnet_assembly,
+	files,
+	game_engines::{
+		game_engine::GameEngineBrand,
+		unity::UnityScriptingBackend,
+	},
+	local_mod,
+	mod_loaders::{
+		bepinex::BepInEx,
+		melonloader::MelonLoader,
+		mod_database::RunnableModData,
+		mod_loader::ModLoaderStatic,
+		runnable_loader::RunnableLoader,
+	},
+	mod_manifest::Manifest,
+	paths,
+	Error,
+	Result,
+};
+
+// Imported mods don't come with any version information.
+const IMPORTED_MOD_VERSION: &str = "imported";
+
+// Folders BepInEx and MelonLoader mods usually ship with, relative to the mod root.
+const BEPINEX_MOD_FOLDERS: [&str; 2] = ["plugins", "patchers"];
+const MELONLOADER_MOD_FOLDERS: [&str; 2] = ["Mods", "Plugins"];
+
+enum ImportedModKind {
+	BepInEx,
+	MelonLoader,
+	Runnable(PathBuf),
+}
+
+// Zips often have everything inside a single folder, or inside a BepInEx folder.
+fn find_mod_root(source_path: &Path) -> Result<PathBuf> {
+	let bepinex_folder = source_path.join("BepInEx");
+	if bepinex_folder.is_dir() {
+		return Ok(bepinex_folder);
+	}
+
+	let entries: Vec<_> = fs::read_dir(source_path)?.collect::<std::io::Result<_>>()?;
+
+	match entries.as_slice() {
+		[single_entry] if single_entry.file_type()?.is_dir() => {
+			let child_path = single_entry.path();
+			let child_name = single_entry.file_name();
+
+			// A lone plugins folder is already the layout we want.
+			if BEPINEX_MOD_FOLDERS
+				.iter()
+				.chain(MELONLOADER_MOD_FOLDERS.iter())
+				.any(|folder_name| child_name == *folder_name)
+			{
+				Ok(source_path.to_path_buf())
+			} else {
+				find_mod_root(&child_path)
+			}
+		}
+		_ => Ok(source_path.to_path_buf()),
+	}
+}
+
+fn get_mod_kind(mod_root: &Path, references: &HashSet<String>) -> Result<ImportedModKind> {
+	if references.contains("MelonLoader") {
+		return Ok(ImportedModKind::MelonLoader);
+	}
+
+	if references
+		.iter()
+		.any(|reference| reference.starts_with("BepInEx"))
+	{
+		return Ok(ImportedModKind::BepInEx);
+	}
+
+	// Falls back to the folder layout, in case the dlls don't reference the loader directly.
+	if mod_root.join("Mods").is_dir() {
+		return Ok(ImportedModKind::MelonLoader);
+	}
+
+	if BEPINEX_MOD_FOLDERS
+		.iter()
+		.any(|folder_name| mod_root.join(folder_name).is_dir())
+	{
+		return Ok(ImportedModKind::BepInEx);
+	}
+
+	if let Some(executable_path) = find_runnable_executable(mod_root)? {
+		return Ok(ImportedModKind::Runnable(executable_path));
+	}
+
+	Err(Error::ModImportUnrecognized(mod_root.to_path_buf()))
+}
+
+// Only looks at the executables closest to the mod root, since the ones in subfolders are usually helpers.
+// If there's more than one of those, we can't know which one to run.
+fn find_runnable_executable(mod_root: &Path) -> Result<Option<PathBuf>> {
+	let executable_paths: Vec<_> = files::find_files_with_extension(mod_root, "exe")?
+		.iter()
+		.map(|executable_path| {
+			executable_path
+				.strip_prefix(mod_root)
+				.unwrap_or(executable_path)
+				.to_path_buf()
+		})
+		.collect();
+
+	let Some(min_depth) = executable_paths
+		.iter()
+		.map(|executable_path| executable_path.components().count())
+		.min()
+	else {
+		return Ok(None);
+	};
+
+	let mut closest_paths: Vec<_> = executable_paths
+		.into_iter()
+		.filter(|executable_path| executable_path.components().count() == min_depth)
+		.collect();
+	closest_paths.sort();
+
+	match closest_paths.as_slice() {
+		[executable_path] => Ok(Some(executable_path.clone())),
+		_ => Err(Error::ModImportExecutableAmbiguous(
+			mod_root.to_path_buf(),
+			closest_paths
+				.iter()
+				.map(|executable_path| executable_path.to_string_lossy())
+				.collect::<Vec<_>>()
+				.join(", "),
+		)),
+	}
+}
+
+// Copies the mod files into the target folder, using the layout the mod loader expects.
+// Files that aren't in any of the expected folders get put in the first one.
+fn copy_mod_files(mod_root: &Path, target_path: &Path, mod_folders: &[&str]) -> Result {
+	let has_mod_folders = mod_folders
+		.iter()
+		.any(|folder_name| mod_root.join(folder_name).is_dir());
+
+	if has_mod_folders {
+		for folder_name in mod_folders {
+			let folder_path = mod_root.join(folder_name);
+			if folder_path.is_dir() {
+				files::copy_dir_all(folder_path, target_path.join(folder_name))?;
+			}
+		}
+	} else {
+		files::copy_dir_all(mod_root, target_path.join(mod_folders[0]))?;
+	}
+
+	Ok(())
+}
+
+// Removes the target folder if anything fails, since a partial copy would block importing the mod again.
+fn write_mod_files<F>(
+	mod_id: &str,
+	target_path: &Path,
+	manifest: &Manifest,
+	copy_files: F,
+) -> Result
+where
+	F: FnOnce() -> Result,
+{
+	if target_path.exists() {
+		return Err(Error::ModAlreadyExists(mod_id.to_string()));
+	}
+
+	let write_result = copy_files().and_then(|()| {
+		fs::write(
+			local_mod::get_manifest_path(target_path),
+			serde_json::to_string_pretty(manifest)?,
+		)?;
+
+		Ok(())
+	});
+
+	if write_result.is_err() && target_path.is_dir() {
+		fs::remove_dir_all(target_path)?;
+	}
+
+	write_result
+}
+
+fn import_from_folder(source_path: &Path, mod_id: &str) -> Result {
+	let mod_root = find_mod_root(source_path)?;
+	let references = dotnet_assembly::get_folder_assembly_references(&mod_root);
+
+	let mod_kind = get_mod_kind(&mod_root, &references)?;
+
+	// Mods that don't reference anything IL2CPP specific are most likely Mono.
+	let unity_backend =
+		dotnet_assembly::get_unity_backend(&references).unwrap_or(UnityScriptingBackend::Mono);
+
+	match mod_kind {
+		ImportedModKind::BepInEx | ImportedModKind::MelonLoader => {
+			let (installed_mods_path, mod_folders) = if matches!(mod_kind, ImportedModKind::BepInEx)
+			{
+				(BepInEx::get_installed_mods_path()?, BEPINEX_MOD_FOLDERS)
+			} else {
+				(
+					MelonLoader::get_installed_mods_path()?,
+					MELONLOADER_MOD_FOLDERS,
+				)
+			};
+
+			let target_path = installed_mods_path
+				.join(unity_backend.to_string())
+				.join(mod_id);
+
+			write_mod_files(
+				mod_id,
+				&target_path,
+				&Manifest {
+					version: IMPORTED_MOD_VERSION.to_string(),
+					runnable: None,
+					engine: Some(GameEngineBrand::Unity),
+					unity_backend: Some(unity_backend),
+					dependencies: Vec::new(),
+					conflicts: Vec::new(),
+					pinned: false,
+					disabled: false,
+					loader_id: None,
+				},
+				|| copy_mod_files(&mod_root, &target_path, &mod_folders),
+			)
+		}
+		ImportedModKind::Runnable(executable_path) => {
+			let target_path = RunnableLoader::get_installed_mods_path()?.join(mod_id);
+
+			write_mod_files(
+				mod_id,
+				&target_path,
+				&Manifest {
+					version: IMPORTED_MOD_VERSION.to_string(),
+					runnable: Some(RunnableModData {
+						path: paths::path_to_str(&executable_path)?.to_string(),
+						args: Vec::new(),
+						conditional_args: Vec::new(),
+					}),
+					engine: None,
+					unity_backend: None,
+					dependencies: Vec::new(),
+					conflicts: Vec::new(),
+					pinned: false,
+					disabled: false,
+					loader_id: None,
+				},
+				|| files::copy_dir_all(&mod_root, &target_path),
+			)
+		}
+	}
+}
+
+// Turns a zip or folder the user picked into a local mod, with a generated manifest.
+// The mod id is taken from the zip or folder name.
+pub fn import_mod(path: &Path) -> Result<String> {
+	let mod_id = paths::file_name_without_extension(path)?.to_string();
+
+	if path.is_dir() {
+		import_from_folder(path, &mod_id)?;
+	} else {
+		let staging_path = paths::installed_mods_path()?.join("imports").join(&mod_id);
+		if staging_path.is_dir() {
+			fs::remove_dir_all(&staging_path)?;
+		}
+
+		files::extract_zip(path, &staging_path, |_, _| {})?;
+		let import_result = import_from_folder(&staging_path, &mod_id);
+		fs::remove_dir_all(&staging_path)?;
+		import_result?;
+	}
+
+	Ok(mod_id)
+}
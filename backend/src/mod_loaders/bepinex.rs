@@ -1,9 +1,9 @@
 use std::{
-	collections::HashMap,
-	fs::{
-		self,
-		File,
+	collections::{
+		HashMap,
+		HashSet,
 	},
+	fs,
 	path::{
 		Path,
 		PathBuf,
@@ -11,34 +11,54 @@ use std::{
 };
 
 use async_trait::async_trait;
-use zip::ZipArchive;
+use log::warn;
 
-use super::mod_loader::ModLoaderStatic;
+use super::{
+	bepinex_config,
+	mod_loader::ModLoaderStatic,
+};
 use crate::{
-	files::copy_dir_all,
-	game_engines::{
-		game_engine::{
-			GameEngine,
-			GameEngineBrand,
-		},
-		unity::UnityScriptingBackend,
+	game_engines::game_engine::GameEngine,
+	game_executable::{
+		Architecture,
+		OperatingSystem,
 	},
 	game_mod::CommonModData,
+	install_journal::InstallJournal,
 	installed_game::InstalledGame,
 	local_mod::{
 		LocalMod,
 		ModKind,
 	},
 	mod_loaders::mod_loader::{
+		self,
 		ModLoaderActions,
 		ModLoaderData,
 	},
+	operating_systems::get_current_os,
 	paths,
+	progress::{
+		ModProgress,
+		ProgressPhase,
+	},
 	serializable_struct,
+	steam::proton,
 	Error,
 	Result,
 };
 
+// Folders inside BepInEx where each mod gets its own subfolder.
+const MOD_FOLDERS: [&str; 2] = ["plugins", "patchers"];
+
+// Files that get added next to the game executable when installing BepInEx.
+const GAME_FOLDER_FILES: [&str; 2] = ["winhttp.dll", "doorstop_config.ini"];
+
+// Script that starts native Linux games with BepInEx, written to the game's installed mods folder.
+const LAUNCH_SCRIPT_NAME: &str = "run_bepinex.sh";
+
+// Everything BepInEx puts in the game's installed mods folder, which is shared with other loaders.
+const INSTALLED_MODS_FOLDER_PATHS: [&str; 3] = ["BepInEx", "doorstop_libs", LAUNCH_SCRIPT_NAME];
+
 serializable_struct!(BepInEx {
 	pub data: ModLoaderData,
 	pub id: &'static str,
@@ -66,51 +86,77 @@ impl ModLoaderActions for BepInEx {
 		&self.data
 	}
 
-	fn install(&self, game: &InstalledGame) -> Result {
-		let scripting_backend_path = &self.data.path.join(
-			game.executable
-				.scripting_backend
-				.ok_or_else(|| {
-					Error::ModInstallInfoInsufficient(
-						"scripting_backend".to_string(),
-						game.executable.path.clone(),
-					)
-				})?
-				.to_string(),
-		);
-		let architecture_path = scripting_backend_path
-			.join(
-				game.executable
-					.operating_system
-					.ok_or_else(|| {
-						Error::ModInstallInfoInsufficient(
-							"operating_system".to_string(),
-							game.executable.path.clone(),
-						)
-					})?
-					.to_string(),
+	fn install<F>(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let scripting_backend = game.executable.scripting_backend.ok_or_else(|| {
+			Error::ModInstallInfoInsufficient(
+				"scripting_backend".to_string(),
+				game.executable.path.clone(),
+			)
+		})?;
+		let scripting_backend_path = &self.data.path.join(scripting_backend.to_string());
+		let operating_system = game.executable.operating_system.ok_or_else(|| {
+			Error::ModInstallInfoInsufficient(
+				"operating_system".to_string(),
+				game.executable.path.clone(),
+			)
+		})?;
+		let architecture = game.executable.architecture.ok_or_else(|| {
+			Error::ModInstallInfoInsufficient(
+				"architecture".to_string(),
+				game.executable.path.clone(),
 			)
-			.join(
-				game.executable
-					.architecture
-					.ok_or_else(|| {
-						Error::ModInstallInfoInsufficient(
-							"architecture".to_string(),
-							game.executable.path.clone(),
-						)
-					})?
-					.to_string(),
-			);
+		})?;
+
+		// The Linux build has the Doorstop libraries for every architecture in the same archive.
+		let operating_system_path = scripting_backend_path.join(operating_system.to_string());
+		let architecture_path = match operating_system {
+			OperatingSystem::Linux => operating_system_path,
+			OperatingSystem::Windows => operating_system_path.join(architecture.to_string()),
+		};
 
 		let mod_loader_archive = architecture_path.join("mod-loader.zip");
+		if !mod_loader_archive.is_file() {
+			return Err(Error::ModLoaderPlatformUnsupported(
+				Self::ID.to_string(),
+				format!("{scripting_backend} {operating_system} {architecture}"),
+			));
+		}
+
 		let folder_to_copy_to_game = architecture_path.join("copy-to-game");
 		let game_data_folder = &game.get_installed_mods_folder()?;
-
-		ZipArchive::new(File::open(mod_loader_archive)?)?.extract(game_data_folder)?;
+		let config_target_folder = bepinex_config::get_config_folder(game)?;
+
+		// Installing overwrites the config files, so we keep what the user had to merge it back in later.
+		let user_configs = bepinex_config::read_all(&config_target_folder)?;
+
+		journal.extract_zip(
+			&mod_loader_archive,
+			game_data_folder,
+			|entries| on_progress(ModProgress::extract_entries(Self::ID, entries)),
+			|current, total| {
+				on_progress(ModProgress::new(
+					Self::ID,
+					ProgressPhase::Extract,
+					current,
+					Some(total),
+				));
+			},
+		)?;
 
 		let game_folder = paths::path_parent(&game.executable.path)?;
 
-		copy_dir_all(folder_to_copy_to_game, game_folder)?;
+		on_progress(ModProgress::new(Self::ID, ProgressPhase::Copy, 0, Some(1)));
+		if folder_to_copy_to_game.is_dir() {
+			journal.copy_dir_all(&folder_to_copy_to_game, game_folder)?;
+		}
 
 		let is_legacy = game.executable.engine.as_ref().map_or(false, is_legacy);
 
@@ -120,58 +166,216 @@ impl ModLoaderActions for BepInEx {
 			"BepInEx.cfg"
 		});
 
-		let config_target_folder = game_data_folder.join("BepInEx").join("config");
-
-		fs::create_dir_all(&config_target_folder)?;
+		journal.copy_file(
+			config_origin_path,
+			&config_target_folder.join("BepInEx.cfg"),
+		)?;
 
-		fs::copy(config_origin_path, config_target_folder.join("BepInEx.cfg"))?;
+		for (config_path, user_config) in user_configs {
+			let new_config = fs::read_to_string(&config_path)?;
+			if new_config != user_config {
+				journal.write_file(
+					&config_path,
+					bepinex_config::merge(&user_config, &new_config),
+				)?;
+			}
+		}
 
-		let doorstop_config =
-			fs::read_to_string(scripting_backend_path.join("doorstop_config.ini"))?;
+		// Keeps the loader disabled if the user had disabled it before.
+		let enabled = !game.is_mod_loader_disabled(Self::ID);
 
-		fs::write(
-			game_folder.join("doorstop_config.ini"),
-			doorstop_config.replace("{{MOD_FILES_PATH}}", paths::path_to_str(game_data_folder)?),
-		)?;
+		// Native Linux games can't load winhttp.dll, so Doorstop gets injected by a launch script instead.
+		if operating_system == OperatingSystem::Linux {
+			let launch_script = fs::read_to_string(architecture_path.join(LAUNCH_SCRIPT_NAME))?;
+			write_launch_script(game, journal, &launch_script, architecture, enabled)?;
+		} else {
+			let doorstop_config =
+				fs::read_to_string(scripting_backend_path.join("doorstop_config.ini"))?;
+
+			journal.write_file(
+				&game_folder.join("doorstop_config.ini"),
+				set_doorstop_enabled(
+					&doorstop_config
+						.replace("{{MOD_FILES_PATH}}", paths::path_to_str(game_data_folder)?),
+					enabled,
+				),
+			)?;
+		}
 
-		// TODO: linux stuff
-		// if let Some(operating_system) = game.executable.operating_system {
-		// 	if get_current_os() != OperatingSystem::Windows
-		// 		&& operating_system == OperatingSystem::Windows
-		// 	{
-		// 		if let Some(steam_launch) = &game.steam_launch {
-		// 			ensure_wine_will_load_bepinex(&game.executable.path, steam_launch.app_id)?;
-		// 		}
-		// 	}
-		// }
+		// Wine only loads BepInEx's winhttp.dll from the game folder if told to.
+		if get_current_os() != OperatingSystem::Windows
+			&& operating_system == OperatingSystem::Windows
+		{
+			if let Some(steam_app_id) = game.steam_app_id {
+				proton::add_dll_override(game, steam_app_id, "winhttp")?;
+			} else {
+				warn!("Can't set up Wine to load BepInEx for `{}`, since it isn't a Steam game. You'll need to add a `winhttp` dll override yourself.", game.name);
+			}
+		}
 
 		Ok(())
 	}
 
-	async fn install_mod_inner(&self, game: &InstalledGame, local_mod: &LocalMod) -> Result {
-		self.install(game)?;
+	async fn install_mod_inner<F>(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let mod_id = &local_mod.common.id;
+
+		// Loader progress is reported as part of the mod that triggered the loader install.
+		self.install(game, journal, |progress| {
+			on_progress(ModProgress {
+				mod_id: mod_id.clone(),
+				..progress
+			});
+		})?;
 
 		let bepinex_folder = game.get_installed_mods_folder()?.join("BepInEx");
 
+		on_progress(ModProgress::new(mod_id, ProgressPhase::Copy, 0, Some(2)));
+
 		let mod_plugin_path = local_mod.data.path.join("plugins");
 		if mod_plugin_path.is_dir() {
-			copy_dir_all(
-				mod_plugin_path,
-				bepinex_folder.join("plugins").join(&local_mod.common.id),
+			journal.copy_dir_all(
+				&mod_plugin_path,
+				&bepinex_folder.join("plugins").join(&local_mod.common.id),
 			)?;
 		}
 
+		on_progress(ModProgress::new(mod_id, ProgressPhase::Copy, 1, Some(2)));
+
 		let mod_patch_path = local_mod.data.path.join("patchers");
 		if mod_patch_path.is_dir() {
-			copy_dir_all(
-				mod_patch_path,
-				bepinex_folder.join("patchers").join(&local_mod.common.id),
+			journal.copy_dir_all(
+				&mod_patch_path,
+				&bepinex_folder.join("patchers").join(&local_mod.common.id),
 			)?;
 		}
 
 		Ok(())
 	}
 
+	fn uninstall(&self, game: &InstalledGame) -> Result {
+		let mut journal = game.open_install_journal()?;
+		let game_folder = paths::path_parent(&game.executable.path)?;
+
+		for file_name in GAME_FOLDER_FILES {
+			journal.remove_file(&game_folder.join(file_name))?;
+		}
+
+		proton::remove_dll_overrides(game)?;
+
+		let game_data_folder = game.get_installed_mods_folder()?;
+
+		// Mods can't run without BepInEx, so they lose their manifests too.
+		for mod_id in get_installed_mod_ids(&game_data_folder.join("BepInEx"))? {
+			game.remove_installed_mod_manifest(&mod_id)?;
+		}
+
+		// Other loaders keep their own files in the installed mods folder too, so those are left alone.
+		for path in INSTALLED_MODS_FOLDER_PATHS {
+			journal.remove_recorded_in(&game_data_folder.join(path))?;
+		}
+
+		if journal.is_empty() && !game.has_installed_mod_manifests()? {
+			game.remove_installed_mods_folder()?;
+		}
+
+		Ok(())
+	}
+
+	fn uninstall_mod_inner(&self, game: &InstalledGame, mod_id: &str) -> Result {
+		let mut journal = game.open_install_journal()?;
+		let bepinex_folder = game.get_installed_mods_folder()?.join("BepInEx");
+
+		for folder_name in MOD_FOLDERS {
+			journal.remove_recorded_in(&bepinex_folder.join(folder_name).join(mod_id))?;
+			journal.remove_recorded_in(
+				&bepinex_folder
+					.join(get_disabled_folder_name(folder_name))
+					.join(mod_id),
+			)?;
+		}
+
+		Ok(())
+	}
+
+	fn set_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		enabled: bool,
+	) -> Result {
+		let launch_script_path = game.get_installed_mods_folder()?.join(LAUNCH_SCRIPT_NAME);
+		if launch_script_path.is_file() {
+			let launch_script = fs::read_to_string(&launch_script_path)?;
+
+			journal.write_file(
+				&launch_script_path,
+				set_launch_script_enabled(&launch_script, enabled),
+			)?;
+		}
+
+		let doorstop_config_path =
+			paths::path_parent(&game.executable.path)?.join("doorstop_config.ini");
+
+		if !doorstop_config_path.is_file() {
+			return Ok(());
+		}
+
+		let doorstop_config = fs::read_to_string(&doorstop_config_path)?;
+
+		journal.write_file(
+			&doorstop_config_path,
+			set_doorstop_enabled(&doorstop_config, enabled),
+		)
+	}
+
+	// BepInEx loads every dll inside the plugins folder, including subfolders,
+	// so disabled mods are moved out to a separate folder.
+	fn set_mod_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		mod_id: &str,
+		enabled: bool,
+	) -> Result {
+		let bepinex_folder = game.get_installed_mods_folder()?.join("BepInEx");
+
+		for folder_name in MOD_FOLDERS {
+			let enabled_folder = bepinex_folder.join(folder_name).join(mod_id);
+			let disabled_folder = bepinex_folder
+				.join(get_disabled_folder_name(folder_name))
+				.join(mod_id);
+
+			let (from, to) = if enabled {
+				(disabled_folder, enabled_folder)
+			} else {
+				(enabled_folder, disabled_folder)
+			};
+
+			if from.is_dir() {
+				// Empty leftovers would stop the folder from being moved on some platforms.
+				if to.is_dir() && fs::read_dir(&to)?.next().is_none() {
+					fs::remove_dir(&to)?;
+				}
+				journal.rename_file(&from, &to)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn get_config_folder(&self, game: &InstalledGame) -> Result<Option<PathBuf>> {
+		Ok(Some(bepinex_config::get_config_folder(game)?))
+	}
+
 	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf> {
 		mod_data.unity_backend.map_or_else(
 			|| Err(Error::UnityBackendUnknown(mod_data.id.clone())),
@@ -186,17 +390,145 @@ impl ModLoaderActions for BepInEx {
 	fn get_local_mods(&self) -> Result<HashMap<String, LocalMod>> {
 		let installed_mods_path = Self::get_installed_mods_path()?;
 
-		let local_mods = {
-			let mut local_mods = find_mods(&installed_mods_path, UnityScriptingBackend::Il2Cpp)?;
-			local_mods.extend(find_mods(
-				&installed_mods_path,
-				UnityScriptingBackend::Mono,
-			)?);
-			local_mods
-		};
+		mod_loader::find_unity_mods(Self::ID, &installed_mods_path)
+	}
+}
 
-		Ok(local_mods)
+fn get_disabled_folder_name(folder_name: &str) -> String {
+	format!("disabled-{folder_name}")
+}
+
+// Each mod has its own folder inside the mod folders, whether it's enabled or not.
+fn get_installed_mod_ids(bepinex_folder: &Path) -> Result<HashSet<String>> {
+	let mut mod_ids = HashSet::new();
+
+	for folder_name in MOD_FOLDERS {
+		for mod_folder in [
+			bepinex_folder.join(folder_name),
+			bepinex_folder.join(get_disabled_folder_name(folder_name)),
+		] {
+			if !mod_folder.is_dir() {
+				continue;
+			}
+
+			for entry in fs::read_dir(mod_folder)? {
+				let entry = entry?;
+				if entry.file_type()?.is_dir() {
+					mod_ids.insert(entry.file_name().to_string_lossy().to_string());
+				}
+			}
+		}
 	}
+
+	Ok(mod_ids)
+}
+
+// Keeps the rest of the file as it was, including its line endings.
+fn set_doorstop_enabled(doorstop_config: &str, enabled: bool) -> String {
+	doorstop_config
+		.split_inclusive('\n')
+		.map(|line| {
+			let content = line.trim_end_matches(['\r', '\n']);
+			let line_ending = &line[content.len()..];
+
+			match content.split_once('=') {
+				Some((key, value)) if key.trim() == "enabled" => {
+					let value_padding = &value[..value.len() - value.trim_start().len()];
+					format!("{key}={value_padding}{enabled}{line_ending}")
+				}
+				_ => line.to_string(),
+			}
+		})
+		.collect()
+}
+
+fn write_launch_script(
+	game: &InstalledGame,
+	journal: &mut InstallJournal,
+	launch_script: &str,
+	architecture: Architecture,
+	enabled: bool,
+) -> Result {
+	let game_data_folder = game.get_installed_mods_folder()?;
+	let launch_script_path = game_data_folder.join(LAUNCH_SCRIPT_NAME);
+
+	journal.write_file(
+		&launch_script_path,
+		set_launch_script_enabled(
+			&launch_script
+				.replace(
+					"{{MOD_FILES_PATH}}",
+					&escape_shell_string(paths::path_to_str(&game_data_folder)?),
+				)
+				.replace(
+					"{{DOORSTOP_LIBRARY}}",
+					&format!("libdoorstop_{}.so", architecture.to_string().to_lowercase()),
+				)
+				.replace(
+					"{{GAME_FOLDER_PATH}}",
+					&escape_shell_string(paths::path_to_str(paths::path_parent(
+						&game.executable.path,
+					)?)?),
+				)
+				.replace(
+					"{{GAME_EXECUTABLE_PATH}}",
+					&escape_shell_string(paths::path_to_str(&game.executable.path)?),
+				),
+			enabled,
+		),
+	)?;
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		fs::set_permissions(&launch_script_path, fs::Permissions::from_mode(0o755))?;
+	}
+
+	Ok(())
+}
+
+fn set_launch_script_enabled(launch_script: &str, enabled: bool) -> String {
+	let mut result = launch_script
+		.lines()
+		.map(|line| {
+			if line.trim_start().starts_with("export DOORSTOP_ENABLE=") {
+				format!(
+					"export DOORSTOP_ENABLE={}",
+					if enabled { "TRUE" } else { "FALSE" }
+				)
+			} else {
+				line.to_string()
+			}
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+	result.push('\n');
+	result
+}
+
+// Makes text safe to put inside double quotes in a shell script.
+fn escape_shell_string(text: &str) -> String {
+	text.chars().fold(String::new(), |mut result, character| {
+		if matches!(character, '"' | '\\' | '$' | '`') {
+			result.push('\\');
+		}
+		result.push(character);
+		result
+	})
+}
+
+// What the user needs to paste in the Steam launch options, so that Steam starts the game through our script.
+pub fn get_steam_launch_options(game: &InstalledGame) -> Result<String> {
+	let launch_script_path = game.get_installed_mods_folder()?.join(LAUNCH_SCRIPT_NAME);
+
+	if !launch_script_path.is_file() {
+		return Err(Error::LaunchScriptNotFound(launch_script_path));
+	}
+
+	Ok(format!(
+		"\"{}\" %command%",
+		escape_shell_string(paths::path_to_str(&launch_script_path)?)
+	))
 }
 
 const fn is_legacy(engine: &GameEngine) -> bool {
@@ -207,107 +539,26 @@ const fn is_legacy(engine: &GameEngine) -> bool {
 	}
 }
 
-// TODO: Linux stuff.
-// fn ensure_wine_will_load_bepinex(game_path: &Path, steam_app_id: u32) -> Result {
-// 	let steam_apps_folder = {
-// 		// I think since canonicalize gets rid of symbolic links then it shouldn't be possible to
-// 		// get stuck in an infinite loop, but who knows. If you're currently stuck in
-// 		// an infinite loop then I'm very sorry, hope infinity goes well for you.
-// 		let mut steam_apps_folder = game_path.canonicalize()?;
-
-// 		loop {
-// 			if steam_apps_folder.ends_with("steamapps") {
-// 				break;
-// 			}
-
-// 			steam_apps_folder = paths::path_parent(&steam_apps_folder)?.to_path_buf();
-// 		}
-// 		steam_apps_folder
-// 	};
-
-// 	let compat_data_dir = steam_apps_folder
-// 		.join("compatdata")
-// 		.join(steam_app_id.to_string());
-// 	let pfx_folder = compat_data_dir.join("pfx");
-// 	let user_reg = pfx_folder.join("user.reg");
-
-// 	let user_reg_data = fs::read_to_string(&user_reg)?;
-
-// 	let ensured_user_reg_data = reg_add_in_section(
-// 		&user_reg_data,
-// 		"[Software\\\\Wine\\\\DllOverrides]",
-// 		"winhttp",
-// 		"native,builtin",
-// 	);
-
-// 	if user_reg_data != ensured_user_reg_data {
-// 		fs::copy(&user_reg, pfx_folder.join("user.reg.bak"))?;
-// 		fs::write(&user_reg, ensured_user_reg_data)?;
-// 	}
-
-// 	Ok(())
-// }
-
-// TODO: Linux stuff.
-// fn reg_add_in_section(reg: &str, section: &str, key: &str, value: &str) -> String {
-// 	let mut split = reg.split('\n').collect::<Vec<_>>();
-
-// 	let mut begin = 0;
-
-// 	for (index, line) in split.iter().enumerate() {
-// 		if line.starts_with(section) {
-// 			begin = index + 2;
-// 			break;
-// 		}
-// 	}
-
-// 	let mut end = 0;
-
-// 	for (index, line) in split.iter().enumerate().skip(begin) {
-// 		if line.is_empty() {
-// 			end = index;
-// 			break;
-// 		}
-// 	}
-
-// 	let line_start = &format!("\"{key}\"");
-// 	let new_line = format!("{line_start}=\"{value}\"");
-
-// 	for (_, line) in split.iter_mut().enumerate().skip(begin) {
-// 		if line.starts_with(line_start) {
-// 			*line = &new_line;
-// 			return split.join("\n");
-// 		}
-// 	}
-
-// 	split.insert(end, &new_line);
-
-// 	split.join("\n")
-// }
-
-fn find_mods(
-	installed_mods_path: &Path,
-	scripting_backend: UnityScriptingBackend,
-) -> Result<HashMap<String, LocalMod>> {
-	let mods_folder_path = installed_mods_path.join(scripting_backend.to_string());
-
-	let entries: Vec<_> = paths::glob_path(&mods_folder_path.join("*"))?.collect();
-
-	Ok(entries
-		.iter()
-		.filter_map(|entry| {
-			entry.as_ref().map_or(None, |mod_path| {
-				if let Ok(local_mod) = LocalMod::new(
-					BepInEx::ID,
-					mod_path,
-					Some(GameEngineBrand::Unity),
-					Some(scripting_backend),
-				) {
-					Some((local_mod.common.id.clone(), local_mod))
-				} else {
-					None
-				}
-			})
-		})
-		.collect())
+#[cfg(test)]
+mod tests {
+	use super::set_doorstop_enabled;
+
+	#[test]
+	fn sets_doorstop_enabled_with_spaces() {
+		assert_eq!(
+			set_doorstop_enabled("[General]\nenabled = true\ntarget_assembly=a.dll\n", false),
+			"[General]\nenabled = false\ntarget_assembly=a.dll\n"
+		);
+	}
+
+	#[test]
+	fn keeps_doorstop_line_endings() {
+		assert_eq!(
+			set_doorstop_enabled(
+				"# enabled=true\r\nenabled=false\r\nredirectOutputLog=false",
+				true
+			),
+			"# enabled=true\r\nenabled=true\r\nredirectOutputLog=false"
+		);
+	}
 }
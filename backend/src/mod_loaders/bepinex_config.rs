@@ -0,0 +1,450 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use crate::{
+	installed_game::InstalledGame,
+	paths,
+	serializable_struct,
+	Error,
+	Result,
+};
+
+serializable_struct!(BepInExConfigRange {
+	pub min: String,
+	pub max: String,
+});
+
+serializable_struct!(BepInExConfigEntry {
+	pub key: String,
+	pub value: String,
+	pub description: Option<String>,
+	pub setting_type: Option<String>,
+	pub default_value: Option<String>,
+	pub acceptable_values: Option<Vec<String>>,
+	pub acceptable_range: Option<BepInExConfigRange>,
+	// Comment lines BepInEx writes that we don't parse, like the hint for flag enums.
+	pub comments: Vec<String>,
+});
+
+serializable_struct!(BepInExConfigSection {
+	pub name: String,
+	pub entries: Vec<BepInExConfigEntry>,
+});
+
+serializable_struct!(BepInExConfig {
+	pub header: Vec<String>,
+	pub sections: Vec<BepInExConfigSection>,
+});
+
+// Flag enums can take several of the acceptable values at once, separated by commas.
+const MULTIPLE_VALUES_COMMENT: &str = "Multiple values can be set at the same time";
+
+// BepInEx parses each setting type with the matching .NET type, so values need to fit in it.
+fn get_type_error(setting_type: &str, value: &str) -> Option<&'static str> {
+	let is_valid = match setting_type {
+		"Boolean" => value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false"),
+		"Byte" => value.parse::<u8>().is_ok(),
+		"SByte" => value.parse::<i8>().is_ok(),
+		"Int16" => value.parse::<i16>().is_ok(),
+		"UInt16" => value.parse::<u16>().is_ok(),
+		"Int32" => value.parse::<i32>().is_ok(),
+		"UInt32" => value.parse::<u32>().is_ok(),
+		"Int64" => value.parse::<i64>().is_ok(),
+		"UInt64" => value.parse::<u64>().is_ok(),
+		"Single" | "Double" | "Decimal" => value.parse::<f64>().is_ok_and(f64::is_finite),
+		_ => true,
+	};
+
+	if is_valid {
+		return None;
+	}
+
+	Some(match setting_type {
+		"Boolean" => "expected true or false",
+		"Single" | "Double" | "Decimal" => "expected a number",
+		_ => "expected a whole number that fits the setting type",
+	})
+}
+
+// Each entry is a single line, so a line break would let a value add other entries to the file.
+fn validate_single_line(key: &str, value: &str) -> Result {
+	if value
+		.chars()
+		.any(|character| character.is_control() && character != '\t')
+	{
+		return Err(Error::ConfigValueInvalid(
+			key.to_string(),
+			value.to_string(),
+			"line breaks and control characters aren't allowed".to_string(),
+		));
+	}
+
+	Ok(())
+}
+
+impl BepInExConfigEntry {
+	const fn new() -> Self {
+		Self {
+			key: String::new(),
+			value: String::new(),
+			description: None,
+			setting_type: None,
+			default_value: None,
+			acceptable_values: None,
+			acceptable_range: None,
+			comments: Vec::new(),
+		}
+	}
+
+	fn accepts_multiple_values(&self) -> bool {
+		self.comments
+			.iter()
+			.any(|comment| comment.starts_with(MULTIPLE_VALUES_COMMENT))
+	}
+
+	fn validate(&self, value: &str) -> Result {
+		let get_invalid_error = |reason: &str| {
+			Error::ConfigValueInvalid(self.key.clone(), value.to_string(), reason.to_string())
+		};
+
+		validate_single_line(&self.key, value)?;
+
+		if let Some(acceptable_values) = &self.acceptable_values {
+			let values: Vec<_> = if self.accepts_multiple_values() {
+				value.split(',').map(str::trim).collect()
+			} else {
+				vec![value.trim()]
+			};
+
+			if let Some(unacceptable_value) = values.iter().find(|single_value| {
+				!acceptable_values
+					.iter()
+					.any(|acceptable| acceptable == *single_value)
+			}) {
+				return Err(get_invalid_error(&format!(
+					"`{unacceptable_value}` isn't one of: {}",
+					acceptable_values.join(", ")
+				)));
+			}
+		}
+
+		if let Some(type_error) = self
+			.setting_type
+			.as_deref()
+			.and_then(|setting_type| get_type_error(setting_type, value))
+		{
+			return Err(get_invalid_error(type_error));
+		}
+
+		if let Some(range) = &self.acceptable_range {
+			let Ok(number) = value.parse::<f64>() else {
+				return Err(get_invalid_error("expected a number"));
+			};
+
+			if let (Ok(min), Ok(max)) = (range.min.parse::<f64>(), range.max.parse::<f64>()) {
+				if number < min || number > max {
+					return Err(get_invalid_error(&format!(
+						"expected a value from {} to {}",
+						range.min, range.max
+					)));
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	// Writes the entry the same way BepInEx does.
+	fn to_lines(&self) -> Vec<String> {
+		let mut lines = Vec::new();
+
+		if let Some(description) = &self.description {
+			lines.extend(description.lines().map(|line| format!("## {line}")));
+		}
+		if let Some(setting_type) = &self.setting_type {
+			lines.push(format!("# Setting type: {setting_type}"));
+		}
+		if let Some(default_value) = &self.default_value {
+			lines.push(format!("# Default value: {default_value}"));
+		}
+		if let Some(acceptable_values) = &self.acceptable_values {
+			lines.push(format!(
+				"# Acceptable values: {}",
+				acceptable_values.join(", ")
+			));
+		}
+		if let Some(range) = &self.acceptable_range {
+			lines.push(format!(
+				"# Acceptable value range: From {} to {}",
+				range.min, range.max
+			));
+		}
+		lines.extend(self.comments.iter().map(|comment| format!("# {comment}")));
+		lines.push(format!("{} = {}", self.key, self.value));
+
+		lines
+	}
+}
+
+impl BepInExConfig {
+	fn get_entry(&self, section_name: &str, key: &str) -> Option<&BepInExConfigEntry> {
+		self.sections
+			.iter()
+			.find(|section| section.name == section_name)?
+			.entries
+			.iter()
+			.find(|entry| entry.key == key)
+	}
+}
+
+fn parse_section_name(line: &str) -> Option<&str> {
+	line.strip_prefix('[')?.strip_suffix(']')
+}
+
+pub fn parse(contents: &str) -> BepInExConfig {
+	let mut config = BepInExConfig {
+		header: Vec::new(),
+		sections: Vec::new(),
+	};
+
+	// Comments come before the entry they describe, so they're collected until we find the entry.
+	let mut entry = BepInExConfigEntry::new();
+	let mut description_lines: Vec<&str> = Vec::new();
+
+	for line in contents.lines().map(str::trim) {
+		if line.is_empty() {
+			continue;
+		}
+
+		if let Some(section_name) = parse_section_name(line) {
+			config.sections.push(BepInExConfigSection {
+				name: section_name.to_string(),
+				entries: Vec::new(),
+			});
+			entry = BepInExConfigEntry::new();
+			description_lines.clear();
+		} else if let Some(description) = line.strip_prefix("##") {
+			if config.sections.is_empty() {
+				config.header.push(description.trim().to_string());
+			} else {
+				description_lines.push(description.trim());
+			}
+		} else if let Some(comment) = line.strip_prefix('#') {
+			let comment = comment.trim();
+
+			if let Some(setting_type) = comment.strip_prefix("Setting type:") {
+				entry.setting_type = Some(setting_type.trim().to_string());
+			} else if let Some(default_value) = comment.strip_prefix("Default value:") {
+				entry.default_value = Some(default_value.trim().to_string());
+			} else if let Some(acceptable_values) = comment.strip_prefix("Acceptable values:") {
+				entry.acceptable_values = Some(
+					acceptable_values
+						.split(',')
+						.map(|value| value.trim().to_string())
+						.collect(),
+				);
+			} else if let Some((min, max)) = comment
+				.strip_prefix("Acceptable value range: From ")
+				.and_then(|range| range.split_once(" to "))
+			{
+				entry.acceptable_range = Some(BepInExConfigRange {
+					min: min.trim().to_string(),
+					max: max.trim().to_string(),
+				});
+			} else {
+				entry.comments.push(comment.to_string());
+			}
+		} else if let Some((key, value)) = line.split_once('=') {
+			entry.key = key.trim().to_string();
+			entry.value = value.trim().to_string();
+			if !description_lines.is_empty() {
+				entry.description = Some(description_lines.join("\n"));
+			}
+
+			// BepInEx ignores entries outside of a section.
+			if let Some(section) = config.sections.last_mut() {
+				section.entries.push(entry);
+			}
+
+			entry = BepInExConfigEntry::new();
+			description_lines.clear();
+		}
+	}
+
+	config
+}
+
+// Index of the line right after the last non-empty line of the section.
+fn find_section_end(lines: &[String], section_name: &str) -> Option<usize> {
+	let section_start = lines
+		.iter()
+		.position(|line| parse_section_name(line.trim()) == Some(section_name))?;
+
+	let mut section_end = lines
+		.iter()
+		.skip(section_start + 1)
+		.position(|line| parse_section_name(line.trim()).is_some())
+		.map_or(lines.len(), |index| index + section_start + 1);
+
+	while section_end > section_start + 1 && lines[section_end - 1].trim().is_empty() {
+		section_end -= 1;
+	}
+
+	Some(section_end)
+}
+
+fn join_lines(lines: &[String]) -> String {
+	format!("{}\n", lines.join("\n"))
+}
+
+// Only touches the line with the value, so everything else in the file stays as the user left it.
+// The value needs to be valid for the entry's setting type and acceptable values.
+pub fn set_value(contents: &str, section_name: &str, key: &str, value: &str) -> Result<String> {
+	parse(contents)
+		.get_entry(section_name, key)
+		.ok_or_else(|| Error::DataEntryNotFound(format!("{section_name}.{key}")))?
+		.validate(value)?;
+
+	let mut current_section = None;
+	let mut found = false;
+
+	let lines: Vec<String> = contents
+		.lines()
+		.map(|line| {
+			let trimmed_line = line.trim();
+
+			if let Some(section) = parse_section_name(trimmed_line) {
+				current_section = Some(section.to_string());
+			} else if !trimmed_line.starts_with('#')
+				&& current_section.as_deref() == Some(section_name)
+				&& trimmed_line
+					.split_once('=')
+					.is_some_and(|(line_key, _)| line_key.trim() == key)
+			{
+				found = true;
+				return format!("{key} = {value}");
+			}
+
+			line.to_string()
+		})
+		.collect();
+
+	if !found {
+		return Err(Error::DataEntryNotFound(format!("{section_name}.{key}")));
+	}
+
+	Ok(join_lines(&lines))
+}
+
+// Keeps everything the user had, and adds any entries that only exist in the new config.
+pub fn merge(user_contents: &str, new_contents: &str) -> String {
+	let user_config = parse(user_contents);
+	let new_config = parse(new_contents);
+
+	let mut lines: Vec<String> = user_contents.lines().map(ToString::to_string).collect();
+
+	for section in &new_config.sections {
+		let missing_entries: Vec<_> = section
+			.entries
+			.iter()
+			.filter(|entry| user_config.get_entry(&section.name, &entry.key).is_none())
+			.collect();
+
+		if missing_entries.is_empty() {
+			continue;
+		}
+
+		let entry_lines = missing_entries.iter().flat_map(|entry| {
+			let mut entry_lines = vec![String::new()];
+			entry_lines.extend(entry.to_lines());
+			entry_lines
+		});
+
+		if let Some(section_end) = find_section_end(&lines, &section.name) {
+			lines.splice(section_end..section_end, entry_lines);
+		} else {
+			if !lines.is_empty() {
+				lines.push(String::new());
+			}
+			lines.push(format!("[{}]", section.name));
+			lines.extend(entry_lines);
+		}
+	}
+
+	join_lines(&lines)
+}
+
+pub fn get_config_folder(game: &InstalledGame) -> Result<PathBuf> {
+	Ok(game
+		.get_installed_mods_folder()?
+		.join("BepInEx")
+		.join("config"))
+}
+
+// Reads every config file, so that user changes can be merged back in after reinstalling.
+pub fn read_all(config_folder: &Path) -> Result<HashMap<PathBuf, String>> {
+	let mut configs = HashMap::new();
+
+	for config_path in paths::glob_path(&config_folder.join("*.cfg"))?.flatten() {
+		configs.insert(config_path.clone(), fs::read_to_string(config_path)?);
+	}
+
+	Ok(configs)
+}
+
+// Only allows plain file names, so the path can't point outside the config folder.
+fn get_config_path(game: &InstalledGame, file_name: &str) -> Result<PathBuf> {
+	let config_path = get_config_folder(game)?.join(file_name);
+
+	if Path::new(file_name)
+		.file_name()
+		.and_then(|name| name.to_str())
+		!= Some(file_name)
+		|| !config_path.is_file()
+	{
+		return Err(Error::DataEntryNotFound(file_name.to_string()));
+	}
+
+	Ok(config_path)
+}
+
+pub fn get_file_names(game: &InstalledGame) -> Result<Vec<String>> {
+	let mut file_names: Vec<_> = read_all(&get_config_folder(game)?)?
+		.keys()
+		.filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+		.collect();
+
+	file_names.sort();
+
+	Ok(file_names)
+}
+
+pub fn read(game: &InstalledGame, file_name: &str) -> Result<BepInExConfig> {
+	Ok(parse(&fs::read_to_string(get_config_path(
+		game, file_name,
+	)?)?))
+}
+
+pub fn write_value(
+	game: &InstalledGame,
+	file_name: &str,
+	section_name: &str,
+	key: &str,
+	value: &str,
+) -> Result {
+	let config_path = get_config_path(game, file_name)?;
+	let contents = fs::read_to_string(&config_path)?;
+
+	fs::write(
+		&config_path,
+		set_value(&contents, section_name, key, value.trim())?,
+	)?;
+
+	Ok(())
+}
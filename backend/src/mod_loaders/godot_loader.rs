@@ -0,0 +1,283 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use async_trait::async_trait;
+
+use super::mod_loader::ModLoaderStatic;
+use crate::{
+	game_engines::game_engine::GameEngineBrand,
+	game_mod::CommonModData,
+	install_journal::InstallJournal,
+	installed_game::InstalledGame,
+	local_mod::{
+		LocalMod,
+		ModKind,
+	},
+	mod_loaders::mod_loader::{
+		self,
+		ModLoaderActions,
+		ModLoaderData,
+	},
+	paths,
+	progress::{
+		ModProgress,
+		ProgressPhase,
+	},
+	serializable_struct,
+	Error,
+	Result,
+};
+
+// Godot reads this file from the game folder on startup, and merges it into the project settings.
+// We use it to add an autoload script that loads the pck files of every installed mod.
+const OVERRIDE_CONFIG_FILE: &str = "override.cfg";
+const AUTOLOAD_NAME: &str = "RaiPalModLoader";
+
+// ProjectSettings.load_resource_pack only exists since Godot 3.
+const MIN_ENGINE_MAJOR_VERSION: u32 = 3;
+
+serializable_struct!(GodotLoader {
+	pub data: ModLoaderData,
+	pub id: &'static str,
+});
+
+#[async_trait]
+impl ModLoaderStatic for GodotLoader {
+	const ID: &'static str = "godot";
+
+	async fn new(resources_path: &Path) -> Result<Self> {
+		Ok(Self {
+			id: Self::ID,
+			data: ModLoaderData {
+				id: Self::ID.to_string(),
+				path: resources_path.join(Self::ID),
+				kind: ModKind::Installable,
+			},
+		})
+	}
+}
+
+fn get_godot_folder(game: &InstalledGame) -> Result<PathBuf> {
+	Ok(game.get_installed_mods_folder()?.join("Godot"))
+}
+
+fn get_script_path(game: &InstalledGame) -> Result<PathBuf> {
+	Ok(get_godot_folder(game)?.join("rai_pal_mod_loader.gd"))
+}
+
+// Godot wants forward slashes, even on Windows.
+fn to_godot_path(path: &Path) -> Result<String> {
+	Ok(paths::path_to_str(path)?
+		.replace('\\', "/")
+		.replace('"', "\\\""))
+}
+
+fn remove_autoload(override_config: &str) -> Vec<String> {
+	override_config
+		.lines()
+		.filter(|line| !line.starts_with(AUTOLOAD_NAME))
+		.map(ToString::to_string)
+		.collect()
+}
+
+// Adds our autoload to the [autoload] section, keeping whatever else was already in the file.
+fn add_autoload(override_config: &str, script_path: &str) -> String {
+	let autoload_line = format!("{AUTOLOAD_NAME}=\"*{script_path}\"");
+
+	let mut lines = remove_autoload(override_config);
+
+	if let Some(section_index) = lines.iter().position(|line| line.trim() == "[autoload]") {
+		lines.insert(section_index + 1, autoload_line);
+	} else {
+		if !lines.is_empty() {
+			lines.push(String::new());
+		}
+		lines.push("[autoload]".to_string());
+		lines.push(autoload_line);
+	}
+
+	format!("{}\n", lines.join("\n"))
+}
+
+// Regenerates the autoload script, so that it loads the pck files of all installed mods.
+fn write_loader_script(game: &InstalledGame, journal: &mut InstallJournal) -> Result {
+	let pck_paths =
+		paths::glob_path(&get_godot_folder(game)?.join("mods").join("*").join("*.pck"))?;
+
+	let mut script =
+		String::from("extends Node\n\n# Generated by Rai Pal.\nfunc _init():\n\tpass\n");
+
+	for pck_path in pck_paths.flatten() {
+		script.push_str(&format!(
+			"\tProjectSettings.load_resource_pack(\"{}\")\n",
+			to_godot_path(&pck_path)?
+		));
+	}
+
+	journal.write_file(&get_script_path(game)?, script)
+}
+
+#[async_trait]
+impl ModLoaderActions for GodotLoader {
+	fn get_data(&self) -> &ModLoaderData {
+		&self.data
+	}
+
+	fn install<F>(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let engine_version = game
+			.executable
+			.engine
+			.as_ref()
+			.and_then(|engine| engine.version.as_ref())
+			.ok_or_else(|| {
+				Error::ModInstallInfoInsufficient(
+					"engine_version".to_string(),
+					game.executable.path.clone(),
+				)
+			})?;
+
+		if engine_version.major < MIN_ENGINE_MAJOR_VERSION {
+			return Err(Error::EngineVersionNotSupported(
+				GameEngineBrand::Godot.to_string(),
+				engine_version.display.clone(),
+			));
+		}
+
+		on_progress(ModProgress::new(Self::ID, ProgressPhase::Copy, 0, Some(1)));
+
+		write_loader_script(game, journal)?;
+
+		// Keeps the loader disabled if the user had disabled it before.
+		self.set_enabled_inner(game, journal, !game.is_mod_loader_disabled(Self::ID))
+	}
+
+	async fn install_mod_inner<F>(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let mod_id = &local_mod.common.id;
+
+		on_progress(ModProgress::new(mod_id, ProgressPhase::Copy, 0, Some(1)));
+
+		let mod_folder = get_godot_folder(game)?.join("mods").join(mod_id);
+
+		for pck_path in paths::glob_path(&local_mod.data.path.join("*.pck"))?.flatten() {
+			if let Some(file_name) = pck_path.file_name() {
+				journal.copy_file(&pck_path, &mod_folder.join(file_name))?;
+			}
+		}
+
+		// Installing the loader after copying the pck files,
+		// so that the loader script includes this mod.
+		self.install(game, journal, |progress| {
+			on_progress(ModProgress {
+				mod_id: mod_id.clone(),
+				..progress
+			});
+		})
+	}
+
+	fn uninstall(&self, game: &InstalledGame) -> Result {
+		let mut journal = game.open_install_journal()?;
+
+		journal
+			.remove_file(&paths::path_parent(&game.executable.path)?.join(OVERRIDE_CONFIG_FILE))?;
+
+		let godot_folder = get_godot_folder(game)?;
+		if godot_folder.is_dir() {
+			fs::remove_dir_all(godot_folder)?;
+		}
+
+		Ok(())
+	}
+
+	fn uninstall_mod_inner(&self, game: &InstalledGame, mod_id: &str) -> Result {
+		let mod_folder = get_godot_folder(game)?.join("mods").join(mod_id);
+		if mod_folder.is_dir() {
+			fs::remove_dir_all(mod_folder)?;
+		}
+
+		if get_script_path(game)?.is_file() {
+			write_loader_script(game, &mut game.open_install_journal()?)?;
+		}
+
+		Ok(())
+	}
+
+	fn set_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		enabled: bool,
+	) -> Result {
+		let override_config_path =
+			paths::path_parent(&game.executable.path)?.join(OVERRIDE_CONFIG_FILE);
+
+		// Nothing to toggle if the loader isn't installed in this game.
+		if !get_script_path(game)?.is_file() {
+			return Ok(());
+		}
+
+		let override_config = if override_config_path.is_file() {
+			fs::read_to_string(&override_config_path)?
+		} else {
+			String::new()
+		};
+
+		let new_override_config = if enabled {
+			add_autoload(&override_config, &to_godot_path(&get_script_path(game)?)?)
+		} else {
+			remove_autoload(&override_config).join("\n")
+		};
+
+		journal.write_file(&override_config_path, new_override_config)
+	}
+
+	// The loader script only loads files ending in .pck, so disabled ones are renamed.
+	fn set_mod_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		mod_id: &str,
+		enabled: bool,
+	) -> Result {
+		let mod_folder = get_godot_folder(game)?.join("mods").join(mod_id);
+
+		for pck_path in paths::glob_path(&mod_folder.join("*.pck*"))?.flatten() {
+			let enabled_path = mod_loader::get_enabled_path(&pck_path);
+			mod_loader::set_file_enabled(journal, &enabled_path, enabled)?;
+		}
+
+		write_loader_script(game, journal)
+	}
+
+	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf> {
+		Ok(Self::get_installed_mods_path()?.join(&mod_data.id))
+	}
+
+	fn get_local_mods(&self) -> Result<HashMap<String, LocalMod>> {
+		let installed_mods_path = Self::get_installed_mods_path()?;
+
+		mod_loader::find_engine_mods(Self::ID, &installed_mods_path, GameEngineBrand::Godot)
+	}
+}
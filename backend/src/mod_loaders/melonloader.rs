@@ -0,0 +1,243 @@
+use std::{
+	collections::HashMap,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use async_trait::async_trait;
+
+use super::mod_loader::ModLoaderStatic;
+use crate::{
+	game_mod::CommonModData,
+	install_journal::InstallJournal,
+	installed_game::InstalledGame,
+	local_mod::{
+		LocalMod,
+		ModKind,
+	},
+	mod_loaders::mod_loader::{
+		self,
+		ModLoaderActions,
+		ModLoaderData,
+	},
+	paths,
+	progress::{
+		ModProgress,
+		ProgressPhase,
+	},
+	serializable_struct,
+	Error,
+	Result,
+};
+
+// Files that get added next to the game executable when installing MelonLoader.
+const GAME_FOLDER_FILES: [&str; 1] = ["version.dll"];
+
+// Folders (relative to the game folder) where MelonLoader looks for mods.
+// A mod's files are copied from the folders with the same name in the mod's local folder.
+const MOD_FOLDERS: [&str; 2] = ["Mods", "Plugins"];
+
+serializable_struct!(MelonLoader {
+	pub data: ModLoaderData,
+	pub id: &'static str,
+});
+
+#[async_trait]
+impl ModLoaderStatic for MelonLoader {
+	const ID: &'static str = "melonloader";
+
+	async fn new(resources_path: &Path) -> Result<Self> {
+		Ok(Self {
+			id: Self::ID,
+			data: ModLoaderData {
+				id: Self::ID.to_string(),
+				path: resources_path.join(Self::ID),
+				kind: ModKind::Installable,
+			},
+		})
+	}
+}
+
+impl MelonLoader {
+	fn get_architecture_path(&self, game: &InstalledGame) -> Result<PathBuf> {
+		let get_missing_info_error = |info: &str| {
+			Error::ModInstallInfoInsufficient(info.to_string(), game.executable.path.clone())
+		};
+
+		Ok(self
+			.data
+			.path
+			.join(
+				game.executable
+					.scripting_backend
+					.ok_or_else(|| get_missing_info_error("scripting_backend"))?
+					.to_string(),
+			)
+			.join(
+				game.executable
+					.operating_system
+					.ok_or_else(|| get_missing_info_error("operating_system"))?
+					.to_string(),
+			)
+			.join(
+				game.executable
+					.architecture
+					.ok_or_else(|| get_missing_info_error("architecture"))?
+					.to_string(),
+			))
+	}
+}
+
+#[async_trait]
+impl ModLoaderActions for MelonLoader {
+	fn get_data(&self) -> &ModLoaderData {
+		&self.data
+	}
+
+	fn install<F>(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let mod_loader_archive = self.get_architecture_path(game)?.join("mod-loader.zip");
+		let game_folder = paths::path_parent(&game.executable.path)?;
+
+		journal.extract_zip(
+			&mod_loader_archive,
+			game_folder,
+			|entries| on_progress(ModProgress::extract_entries(Self::ID, entries)),
+			|current, total| {
+				on_progress(ModProgress::new(
+					Self::ID,
+					ProgressPhase::Extract,
+					current,
+					Some(total),
+				));
+			},
+		)?;
+
+		// Extracting the loader again would otherwise re-enable it.
+		if game.is_mod_loader_disabled(Self::ID) {
+			self.set_enabled_inner(game, journal, false)?;
+		}
+
+		Ok(())
+	}
+
+	async fn install_mod_inner<F>(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let mod_id = &local_mod.common.id;
+
+		// Loader progress is reported as part of the mod that triggered the loader install.
+		self.install(game, journal, |progress| {
+			on_progress(ModProgress {
+				mod_id: mod_id.clone(),
+				..progress
+			});
+		})?;
+
+		let game_folder = paths::path_parent(&game.executable.path)?;
+		let mut installed_files = Vec::new();
+
+		for (index, folder_name) in MOD_FOLDERS.iter().enumerate() {
+			on_progress(ModProgress::new(
+				mod_id,
+				ProgressPhase::Copy,
+				u64::try_from(index).unwrap_or_default(),
+				Some(u64::try_from(MOD_FOLDERS.len()).unwrap_or_default()),
+			));
+
+			let mod_folder = local_mod.data.path.join(folder_name);
+			if mod_folder.is_dir() {
+				installed_files
+					.extend(journal.copy_dir_all(&mod_folder, &game_folder.join(folder_name))?);
+			}
+		}
+
+		// MelonLoader only loads mods directly from its own folders in the game directory,
+		// so we need to remember which files came from this mod.
+		mod_loader::write_installed_files_list(game, journal, Self::ID, mod_id, &installed_files)
+	}
+
+	fn uninstall(&self, game: &InstalledGame) -> Result {
+		let mut journal = game.open_install_journal()?;
+		let game_folder = paths::path_parent(&game.executable.path)?;
+
+		for file_name in GAME_FOLDER_FILES {
+			let file_path = game_folder.join(file_name);
+			journal.remove_file(&file_path)?;
+			journal.remove_file(&mod_loader::get_disabled_path(&file_path))?;
+		}
+
+		// Logs and anything else MelonLoader generates at runtime are left alone.
+		journal.remove_recorded_in(&game_folder.join("MelonLoader"))?;
+
+		// The mods themselves should have been uninstalled before this,
+		// so this only removes the mod folders if nothing else was left in there.
+		for folder_name in MOD_FOLDERS {
+			journal.remove_recorded_in(&game_folder.join(folder_name))?;
+		}
+
+		mod_loader::remove_installed_files_lists(&mut journal, game, Self::ID)
+	}
+
+	fn uninstall_mod_inner(&self, game: &InstalledGame, mod_id: &str) -> Result {
+		mod_loader::remove_installed_files(game, Self::ID, mod_id)
+	}
+
+	fn set_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		enabled: bool,
+	) -> Result {
+		let game_folder = paths::path_parent(&game.executable.path)?;
+
+		for file_name in GAME_FOLDER_FILES {
+			mod_loader::set_file_enabled(journal, &game_folder.join(file_name), enabled)?;
+		}
+
+		Ok(())
+	}
+
+	fn set_mod_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		mod_id: &str,
+		enabled: bool,
+	) -> Result {
+		mod_loader::set_installed_files_enabled(game, journal, Self::ID, mod_id, enabled)
+	}
+
+	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf> {
+		mod_data.unity_backend.map_or_else(
+			|| Err(Error::UnityBackendUnknown(mod_data.id.clone())),
+			|unity_backend| {
+				Ok(Self::get_installed_mods_path()?
+					.join(unity_backend.to_string())
+					.join(&mod_data.id))
+			},
+		)
+	}
+
+	fn get_local_mods(&self) -> Result<HashMap<String, LocalMod>> {
+		let installed_mods_path = Self::get_installed_mods_path()?;
+
+		mod_loader::find_unity_mods(Self::ID, &installed_mods_path)
+	}
+}
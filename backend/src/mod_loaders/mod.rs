@@ -1,4 +1,8 @@
 pub mod bepinex;
+pub mod bepinex_config;
+pub mod godot_loader;
+pub mod melonloader;
 pub mod mod_database;
 pub mod mod_loader;
 pub mod runnable_loader;
+pub mod unreal_loader;
@@ -1,16 +1,45 @@
-use std::path::PathBuf;
+use std::{
+	collections::HashSet,
+	fmt,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::warn;
+use reqwest::{
+	header::{
+		ETAG,
+		IF_MODIFIED_SINCE,
+		IF_NONE_MATCH,
+		LAST_MODIFIED,
+	},
+	StatusCode,
+};
 
 use crate::{
+	database_sources::{
+		self,
+		ModDatabaseSource,
+	},
+	files,
 	game_engines::{
 		game_engine::GameEngineBrand,
 		unity::UnityScriptingBackend,
 	},
+	game_executable::{
+		Architecture,
+		OperatingSystem,
+	},
+	paths,
 	serializable_struct,
+	signatures,
+	Error,
 	Result,
 };
 
-const URL_BASE: &str = "https://raicuparta.github.io/rai-pal-db";
-
 serializable_struct!(DatabaseEntry {
 	pub id: String,
 	pub title: String,
@@ -18,33 +47,474 @@ serializable_struct!(DatabaseEntry {
 	pub source_code: String,
 	pub description: String,
 	pub latest_version: Option<ModDownload>,
+	#[serde(default)]
+	pub versions: Vec<ModDownload>,
 	pub engine: Option<GameEngineBrand>,
 	pub unity_backend: Option<UnityScriptingBackend>,
+	// Ids of mods that need to be installed before this one.
+	#[serde(default)]
+	pub dependencies: Vec<String>,
+	// Ids of mods that can't be installed in the same game as this one.
+	#[serde(default)]
+	pub conflicts: Vec<String>,
 });
 
 serializable_struct!(RunnableModData {
 	pub path: String,
 	pub args: Vec<String>,
+	// Extra args that only get added for games that match the condition.
+	#[serde(default)]
+	pub conditional_args: Vec<RunnableConditionalArgs>,
+});
+
+// Every field that's set needs to match the game, fields that aren't set match any game.
+serializable_struct!(RunnableArgsCondition {
+	pub engine: Option<GameEngineBrand>,
+	pub engine_major_version: Option<u32>,
+	pub operating_system: Option<OperatingSystem>,
+	pub architecture: Option<Architecture>,
+});
+
+serializable_struct!(RunnableConditionalArgs {
+	pub condition: RunnableArgsCondition,
+	pub args: Vec<String>,
 });
 
 serializable_struct!(ModDatabase {
+  // Missing in databases made before the schema was versioned, which are the same as version 1.
+  #[serde(default = "default_schema_version")]
+  pub schema_version: u32,
   pub mods: Vec<DatabaseEntry>,
 });
 
+// Same as ModDatabase, but each entry is parsed separately so one bad entry doesn't break the whole database.
+serializable_struct!(RawModDatabase {
+  #[serde(default = "default_schema_version")]
+  pub schema_version: u32,
+  pub mods: Vec<serde_json::Value>,
+});
+
+serializable_struct!(ModDatabaseIssue {
+	// Position of the entry in the mods list, if the issue is about a single entry.
+	pub entry_index: Option<usize>,
+	pub mod_id: Option<String>,
+	pub message: String,
+});
+
+pub struct ParsedModDatabase {
+	pub database: ModDatabase,
+	// Problems that didn't stop the database from being used, like entries that had to be skipped.
+	pub issues: Vec<ModDatabaseIssue>,
+}
+
 serializable_struct!(ModDownload {
 	pub id: String,
 	pub url: String,
+	pub sha256: Option<String>,
+	// Contents of the minisign signature file for the download.
+	// Required if the database source has public keys.
+	pub signature: Option<String>,
 	pub root: Option<PathBuf>,
 	pub runnable: Option<RunnableModData>,
 });
 
-pub async fn get(mod_loader_id: &str) -> Result<ModDatabase> {
-	let random = rand::random::<u32>();
+// Newest schema version this app knows how to read.
+pub const SCHEMA_VERSION: u32 = 1;
+
+const fn default_schema_version() -> u32 {
+	1
+}
+
+impl fmt::Display for ModDatabaseIssue {
+	fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match (self.entry_index, &self.mod_id) {
+			(Some(index), Some(mod_id)) => write!(formatter, "mods[{index}] ({mod_id}): "),
+			(Some(index), None) => write!(formatter, "mods[{index}]: "),
+			(None, Some(mod_id)) => write!(formatter, "{mod_id}: "),
+			(None, None) => Ok(()),
+		}?;
+
+		write!(formatter, "{}", self.message)
+	}
+}
+
+impl ModDatabaseIssue {
+	fn new(entry_index: Option<usize>, mod_id: Option<&str>, message: impl Into<String>) -> Self {
+		Self {
+			entry_index,
+			mod_id: mod_id.map(ToString::to_string),
+			message: message.into(),
+		}
+	}
+}
+
+// Only fails if the file itself can't be read as a database.
+// Entries that don't match the schema are skipped and reported as issues.
+pub fn parse(text: &str) -> Result<ParsedModDatabase> {
+	let raw_database: RawModDatabase = serde_json::from_str(text)?;
+	let mut issues = Vec::new();
+
+	if raw_database.schema_version > SCHEMA_VERSION {
+		issues.push(ModDatabaseIssue::new(
+			None,
+			None,
+			format!(
+				"Database uses schema version {}, but this version of Rai Pal only supports up to {SCHEMA_VERSION}. Some mods might be skipped.",
+				raw_database.schema_version
+			),
+		));
+	}
+
+	let mut mods = Vec::new();
+
+	for (entry_index, raw_entry) in raw_database.mods.into_iter().enumerate() {
+		let mod_id = raw_entry
+			.get("id")
+			.and_then(serde_json::Value::as_str)
+			.map(ToString::to_string);
+
+		match serde_json::from_value::<DatabaseEntry>(raw_entry) {
+			Ok(entry) => {
+				// Ids end up in folder names, so an entry with unsafe ids can't be used at all.
+				let id_issues = get_unsafe_id_issues(entry_index, &entry);
+				if id_issues.is_empty() {
+					mods.push(entry);
+				} else {
+					issues.extend(id_issues);
+				}
+			}
+			Err(error) => issues.push(ModDatabaseIssue::new(
+				Some(entry_index),
+				mod_id.as_deref(),
+				format!("Skipped invalid entry: {error}"),
+			)),
+		}
+	}
+
+	Ok(ParsedModDatabase {
+		database: ModDatabase {
+			schema_version: raw_database.schema_version,
+			mods,
+		},
+		issues,
+	})
+}
+
+fn is_valid_sha256(hash: &str) -> bool {
+	hash.len() == 64 && hash.chars().all(|character| character.is_ascii_hexdigit())
+}
+
+fn get_unsafe_id_issues(entry_index: usize, entry: &DatabaseEntry) -> Vec<ModDatabaseIssue> {
+	let mut issues = Vec::new();
+
+	if !files::is_safe_file_name(&entry.id) {
+		issues.push(ModDatabaseIssue::new(
+			Some(entry_index),
+			Some(&entry.id),
+			"Skipped entry, mod id can't be used as a folder name.",
+		));
+	}
+
+	for version in entry.latest_version.iter().chain(entry.versions.iter()) {
+		if !files::is_safe_file_name(&version.id) {
+			issues.push(ModDatabaseIssue::new(
+				Some(entry_index),
+				Some(&entry.id),
+				format!(
+					"Skipped entry, version id `{}` can't be used as a folder name.",
+					version.id
+				),
+			));
+		}
+	}
+
+	issues
+}
+
+fn validate_entry(entry_index: usize, entry: &DatabaseEntry) -> Vec<ModDatabaseIssue> {
+	let mut issues = get_unsafe_id_issues(entry_index, entry);
+	let mut add_issue = |message: String| {
+		issues.push(ModDatabaseIssue::new(
+			Some(entry_index),
+			Some(&entry.id),
+			message,
+		));
+	};
+
+	if entry.latest_version.is_none() && entry.versions.is_empty() {
+		add_issue("Mod doesn't have any downloads.".to_string());
+	}
+
+	let mut version_ids = HashSet::new();
+	for version in entry.latest_version.iter().chain(entry.versions.iter()) {
+		if !version_ids.insert(version.id.as_str()) {
+			add_issue(format!(
+				"Version `{}` is listed more than once.",
+				version.id
+			));
+		}
+		if reqwest::Url::parse(&version.url).is_err() {
+			add_issue(format!(
+				"Version `{}` has an invalid url `{}`.",
+				version.id, version.url
+			));
+		}
+		if let Some(root) = &version.root {
+			if let Err(error) = files::join_safe_subpath(Path::new(""), root) {
+				add_issue(format!(
+					"Version `{}` has an invalid root: {error}",
+					version.id
+				));
+			}
+		}
+		if let Some(signature) = &version.signature {
+			if let Err(error) = signatures::parse_signature(signature) {
+				add_issue(format!(
+					"Version `{}` has an invalid signature: {error}",
+					version.id
+				));
+			}
+		}
+		if let Some(sha256) = &version.sha256 {
+			if !is_valid_sha256(sha256) {
+				add_issue(format!(
+					"Version `{}` has an invalid sha256 hash `{sha256}`.",
+					version.id
+				));
+			}
+		}
+	}
+
+	if entry.dependencies.contains(&entry.id) {
+		add_issue("Mod depends on itself.".to_string());
+	}
+
+	if entry.conflicts.contains(&entry.id) {
+		add_issue("Mod conflicts with itself.".to_string());
+	}
+
+	issues
+}
+
+// Stricter than `parse`, meant for checking a database before publishing it.
+pub fn validate(text: &str) -> Result<Vec<ModDatabaseIssue>> {
+	let parsed_database = parse(text)?;
+	let mut issues = parsed_database.issues;
+	let mut mod_ids = HashSet::new();
+
+	for (entry_index, entry) in parsed_database.database.mods.iter().enumerate() {
+		if !mod_ids.insert(entry.id.as_str()) {
+			issues.push(ModDatabaseIssue::new(
+				Some(entry_index),
+				Some(&entry.id),
+				"Mod id is used more than once.",
+			));
+		}
+
+		issues.extend(validate_entry(entry_index, entry));
+	}
+
+	Ok(issues)
+}
+
+pub fn validate_file(path: &Path) -> Result<Vec<ModDatabaseIssue>> {
+	validate(&fs::read_to_string(path)?)
+}
+
+// Local copy of a database, so the mods are still available when offline.
+// Keeps the original text and signature, so it can be verified again every time it's used.
+serializable_struct!(CachedModDatabase {
+	pub text: String,
+	pub signature: Option<String>,
+	pub etag: Option<String>,
+	pub last_modified: Option<String>,
+	// Unix timestamp, in seconds, of the last time we confirmed this copy was up to date.
+	pub fetched_at: i64,
+});
+
+pub struct FetchedModDatabase {
+	pub database: ModDatabase,
+	pub issues: Vec<ModDatabaseIssue>,
+	// Set if the online database couldn't be reached, and we're using a cached copy instead.
+	pub cached_at: Option<i64>,
+}
+
+fn get_cache_path(source: &ModDatabaseSource, mod_loader_id: &str) -> Result<PathBuf> {
+	Ok(paths::app_data_path()?
+		.join("mod-database-cache")
+		.join(&source.id)
+		.join(format!("{mod_loader_id}.json")))
+}
+
+// Copies that don't pass verification with the source's current keys are ignored,
+// since the keys might have changed after the copy was cached.
+fn read_cache(
+	url: &str,
+	source: &ModDatabaseSource,
+	mod_loader_id: &str,
+) -> Option<CachedModDatabase> {
+	let cache_path = get_cache_path(source, mod_loader_id).ok()?;
+	let cache_text = fs::read_to_string(&cache_path).ok()?;
+
+	serde_json::from_str::<CachedModDatabase>(&cache_text)
+		.map_err(|error| {
+			warn!(
+				"Ignoring invalid mod database cache in {}. Error: {error}",
+				cache_path.display()
+			);
+		})
+		.ok()
+		.filter(|cached| {
+			verify_signature(url, &cached.text, cached.signature.as_deref(), source)
+				.map_err(|error| {
+					warn!(
+						"Ignoring unverified mod database cache in {}. Error: {error}",
+						cache_path.display()
+					);
+				})
+				.is_ok()
+		})
+}
+
+fn write_cache(
+	source: &ModDatabaseSource,
+	mod_loader_id: &str,
+	cached_database: &CachedModDatabase,
+) -> Result {
+	let cache_path = get_cache_path(source, mod_loader_id)?;
+	fs::create_dir_all(paths::path_parent(&cache_path)?)?;
+	fs::write(&cache_path, serde_json::to_string(cached_database)?)?;
+
+	Ok(())
+}
+
+// Sources that don't require signatures don't get their signature downloaded.
+async fn get_signature(url: &str, source: &ModDatabaseSource) -> Result<Option<String>> {
+	if !source.requires_signatures() {
+		return Ok(None);
+	}
+
+	let signature_url = format!("{url}.{}", signatures::SIGNATURE_EXTENSION);
+	let signature = database_sources::get_text(&signature_url)
+		.await
+		.map_err(|error| {
+			warn!("Failed to get signature {signature_url}. Error: {error}");
+			Error::SignatureMissing(url.to_string())
+		})?;
+
+	Ok(Some(signature))
+}
+
+fn verify_signature(
+	url: &str,
+	text: &str,
+	signature: Option<&str>,
+	source: &ModDatabaseSource,
+) -> Result {
+	if !source.requires_signatures() {
+		return Ok(());
+	}
+
+	let signature = signature.ok_or_else(|| Error::SignatureMissing(url.to_string()))?;
+
+	signatures::verify(text.as_bytes(), signature, &source.public_keys, url)
+}
+
+fn parse_fetched(text: &str, cached_at: Option<i64>) -> Result<FetchedModDatabase> {
+	let parsed_database = parse(text)?;
+
+	Ok(FetchedModDatabase {
+		database: parsed_database.database,
+		issues: parsed_database.issues,
+		cached_at,
+	})
+}
+
+// Only downloads the database if it changed since the cached copy.
+async fn fetch(
+	url: &str,
+	source: &ModDatabaseSource,
+	cached_database: Option<&CachedModDatabase>,
+) -> Result<CachedModDatabase> {
+	let mut request = reqwest::Client::new().get(url);
+
+	if let Some(cached) = cached_database {
+		if let Some(etag) = &cached.etag {
+			request = request.header(IF_NONE_MATCH, etag);
+		}
+		if let Some(last_modified) = &cached.last_modified {
+			request = request.header(IF_MODIFIED_SINCE, last_modified);
+		}
+	}
+
+	let response = request.send().await?.error_for_status()?;
+	let fetched_at = chrono::Utc::now().timestamp();
+
+	// The cached copy was already verified when it was read.
+	if response.status() == StatusCode::NOT_MODIFIED {
+		if let Some(cached) = cached_database {
+			return Ok(CachedModDatabase {
+				fetched_at,
+				..cached.clone()
+			});
+		}
+	}
+
+	let get_header = |name| {
+		response
+			.headers()
+			.get(name)
+			.and_then(|value| value.to_str().ok())
+			.map(ToString::to_string)
+	};
+	let etag = get_header(ETAG);
+	let last_modified = get_header(LAST_MODIFIED);
+
+	let text = response.text().await?;
+	let signature = get_signature(url, source).await?;
+	verify_signature(url, &text, signature.as_deref(), source)?;
+
+	Ok(CachedModDatabase {
+		text,
+		signature,
+		etag,
+		last_modified,
+		fetched_at,
+	})
+}
+
+pub async fn get(source: &ModDatabaseSource, mod_loader_id: &str) -> Result<FetchedModDatabase> {
+	let url = database_sources::join_url(&source.url, &format!("{mod_loader_id}.json"));
+
+	// Local databases are always available, so they don't need the cache.
+	if let Some(file_path) = database_sources::get_file_path(&url)? {
+		let text = fs::read_to_string(file_path)?;
+		let signature = get_signature(&url, source).await?;
+		verify_signature(&url, &text, signature.as_deref(), source)?;
+
+		return parse_fetched(&text, None);
+	}
+
+	let cached_database = read_cache(&url, source, mod_loader_id);
+
+	match fetch(&url, source, cached_database.as_ref()).await {
+		Ok(fetched_database) => {
+			if let Err(error) = write_cache(source, mod_loader_id, &fetched_database) {
+				warn!("Failed to cache mod database {url}. Error: {error}");
+			}
+
+			parse_fetched(&fetched_database.text, None)
+		}
+		Err(error) => {
+			let Some(cached) = cached_database else {
+				return Err(error);
+			};
+			warn!(
+				"Failed to fetch mod database {url}, using cached copy from {}. Error: {error}",
+				cached.fetched_at
+			);
 
-	Ok(reqwest::get(format!(
-		"{URL_BASE}/{mod_loader_id}.json?cache_avoider={random}"
-	))
-	.await?
-	.json::<ModDatabase>()
-	.await?)
+			parse_fetched(&cached.text, Some(cached.fetched_at))
+		}
+	}
 }
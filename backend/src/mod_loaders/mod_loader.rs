@@ -1,9 +1,6 @@
 use std::{
 	collections::HashMap,
-	fs::{
-		self,
-		File,
-	},
+	fs,
 	path::{
 		Path,
 		PathBuf,
@@ -13,30 +10,43 @@ use std::{
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
 use log::error;
-use zip::ZipArchive;
 
 use super::{
 	bepinex::BepInEx,
+	godot_loader::GodotLoader,
+	melonloader::MelonLoader,
 	mod_database::{self,},
 	runnable_loader::RunnableLoader,
+	unreal_loader::UnrealLoader,
 };
 use crate::{
+	database_sources::ModDatabaseSource,
+	downloads,
 	files,
+	game_engines::{
+		game_engine::GameEngineBrand,
+		unity::UnityScriptingBackend,
+	},
 	game_mod::CommonModData,
+	install_journal::InstallJournal,
 	installed_game::InstalledGame,
 	local_mod::{
 		self,
 		LocalMod,
 		ModKind,
 	},
-	mod_loaders::mod_database::ModDatabase,
 	mod_manifest,
 	paths,
+	progress::{
+		ModProgress,
+		ProgressPhase,
+	},
 	remote_mod::{
 		RemoteMod,
 		RemoteModData,
 	},
 	serializable_struct,
+	signatures,
 	Error,
 	Result,
 };
@@ -51,18 +61,56 @@ serializable_struct!(ModLoaderData {
 #[derive(Clone)]
 pub enum ModLoader {
 	BepInEx,
+	GodotLoader,
+	MelonLoader,
 	RunnableLoader,
+	UnrealLoader,
 }
 
 #[async_trait]
 #[enum_dispatch(ModLoader)]
 pub trait ModLoaderActions {
-	fn install(&self, game: &InstalledGame) -> Result;
-	async fn install_mod_inner(&self, game: &InstalledGame, local_mod: &LocalMod) -> Result;
+	fn install<F>(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync;
+	async fn install_mod_inner<F>(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync;
+	fn uninstall(&self, game: &InstalledGame) -> Result;
+	fn uninstall_mod_inner(&self, game: &InstalledGame, mod_id: &str) -> Result;
+	fn set_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		enabled: bool,
+	) -> Result;
+	fn set_mod_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		mod_id: &str,
+		enabled: bool,
+	) -> Result;
 	fn get_data(&self) -> &ModLoaderData;
 	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf>;
 	fn get_local_mods(&self) -> Result<HashMap<String, LocalMod>>;
 
+	// Folder with the loader's config files, which get saved in mod profiles.
+	fn get_config_folder(&self, _game: &InstalledGame) -> Result<Option<PathBuf>> {
+		Ok(None)
+	}
+
 	fn open_folder(&self) -> Result {
 		// TODO cleanup code repeated from local_mod.
 		let data = self.get_data();
@@ -75,40 +123,137 @@ pub trait ModLoaderActions {
 		Ok(open::that_detached(path)?)
 	}
 
-	async fn install_mod(&self, game: &InstalledGame, local_mod: &LocalMod) -> Result {
-		self.install_mod_inner(game, local_mod).await?;
+	async fn install_mod<F>(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		// The mod's folder says which backend it's for, but that can be wrong for manually added mods.
+		if let (Some(mod_backend), Some(game_backend)) = (
+			local_mod.data.detected_unity_backend,
+			game.executable.scripting_backend,
+		) {
+			if mod_backend != game_backend {
+				return Err(Error::ModUnityBackendMismatch(
+					local_mod.common.id.clone(),
+					mod_backend.to_string(),
+					game_backend.to_string(),
+				));
+			}
+		}
+
+		let mut journal = game.open_install_journal()?;
+		let checkpoint = journal.checkpoint();
+
+		// If anything fails midway, we undo whatever was written so far,
+		// so the game doesn't end up with a half-installed mod.
+		if let Err(install_error) = self
+			.install_mod_files(game, local_mod, &mut journal, &on_progress)
+			.await
+		{
+			journal.rollback(checkpoint)?;
+			return Err(install_error);
+		}
+
+		on_progress(ModProgress::finished(&local_mod.common.id));
+
+		Ok(())
+	}
+
+	fn uninstall_mod(&self, game: &InstalledGame, mod_id: &str) -> Result {
+		self.uninstall_mod_inner(game, mod_id)?;
+		game.remove_installed_mod_manifest(mod_id)
+	}
+
+	// Disabling a loader keeps all of its files, but stops it from running with the game.
+	fn set_enabled(&self, game: &InstalledGame, enabled: bool) -> Result {
+		self.set_enabled_inner(game, &mut game.open_install_journal()?, enabled)?;
+		game.set_mod_loader_disabled(&self.get_data().id, !enabled)
+	}
+
+	// Disabling a mod keeps its config and manifest, but stops the loader from loading it.
+	fn set_mod_enabled(&self, game: &InstalledGame, mod_id: &str, enabled: bool) -> Result {
+		self.set_mod_enabled_inner(game, &mut game.open_install_journal()?, mod_id, enabled)?;
+		game.set_mod_disabled(mod_id, !enabled)
+	}
+
+	async fn install_mod_files<F>(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		self.install_mod_inner(game, local_mod, journal, &on_progress)
+			.await?;
 
 		if self.get_data().kind != ModKind::Runnable {
 			if let Some(manifest) = &local_mod.data.manifest {
-				let manifest_path = game.get_installed_mod_manifest_path(&local_mod.common.id)?;
-				fs::create_dir_all(paths::path_parent(&manifest_path)?)?;
-				let manifest_contents = serde_json::to_string_pretty(manifest)?;
-				fs::write(manifest_path, manifest_contents)?;
+				journal.write_file(
+					&game.get_installed_mod_manifest_path(&local_mod.common.id)?,
+					serde_json::to_string_pretty(&mod_manifest::Manifest {
+						loader_id: Some(self.get_data().id.clone()),
+						..manifest.clone()
+					})?,
+				)?;
 			}
 		}
 
 		Ok(())
 	}
 
-	async fn get_remote_mods<F>(&self, error_handler: F) -> HashMap<String, RemoteMod>
+	// Sources are in order of precedence, so mods from earlier sources don't get replaced.
+	async fn get_remote_mods<F>(
+		&self,
+		sources: &[ModDatabaseSource],
+		error_handler: F,
+	) -> HashMap<String, RemoteMod>
 	where
 		F: Fn(Error) + Send,
 	{
 		let data = self.get_data();
 		let loader_id = &data.id;
 
-		let database = mod_database::get(loader_id).await.unwrap_or_else(|error| {
-			error_handler(error);
-			ModDatabase { mods: Vec::new() }
-		});
-
-		database
-			.mods
-			.into_iter()
-			.map(|database_mod| {
-				(
-					database_mod.id.clone(),
-					RemoteMod {
+		let mut remote_mods = HashMap::new();
+
+		for source in sources {
+			if !source.has_loader(loader_id) {
+				continue;
+			}
+
+			let fetched_database = match mod_database::get(source, loader_id).await {
+				Ok(fetched_database) => fetched_database,
+				Err(error) => {
+					error_handler(error);
+					continue;
+				}
+			};
+
+			if !fetched_database.issues.is_empty() {
+				for issue in &fetched_database.issues {
+					error!(
+						"Issue in mod database `{}` for {loader_id}: {issue}",
+						source.id
+					);
+				}
+				error_handler(Error::ModDatabaseIssues(
+					source.id.clone(),
+					loader_id.clone(),
+					fetched_database.issues.len(),
+				));
+			}
+
+			for database_mod in fetched_database.database.mods {
+				remote_mods
+					.entry(database_mod.id.clone())
+					.or_insert_with(|| RemoteMod {
 						common: CommonModData {
 							id: database_mod.id,
 							engine: database_mod.engine,
@@ -121,56 +266,221 @@ pub trait ModLoaderActions {
 							source_code: database_mod.source_code,
 							title: database_mod.title,
 							latest_version: database_mod.latest_version,
+							versions: database_mod.versions,
+							dependencies: database_mod.dependencies,
+							conflicts: database_mod.conflicts,
+							source_id: source.id.clone(),
+							signing_keys: source.public_keys.clone(),
+							database_cached_at: fetched_database.cached_at,
 						},
-					},
-				)
-			})
-			.collect()
+					});
+			}
+		}
+
+		remote_mods
 	}
 
-	async fn download_mod(&self, remote_mod: &RemoteMod) -> Result {
-		if let Some(latest_version) = &remote_mod.data.latest_version {
-			let target_path = self.get_mod_path(&remote_mod.common)?;
-			let mod_loader_data = self.get_data();
-			let mod_id = &remote_mod.common.id;
-			let downloads_path = paths::installed_mods_path()?
-				.join(&mod_loader_data.id)
-				.join("downloads");
+	// Both ids can come from a remote database, so they can't be allowed to point outside the versions folder.
+	fn get_mod_version_path(&self, mod_data: &CommonModData, version_id: &str) -> Result<PathBuf> {
+		let versions_path = paths::installed_mods_path()?
+			.join(&self.get_data().id)
+			.join("versions");
 
-			let response = reqwest::get(&latest_version.url).await?;
+		let mod_versions_path = files::join_safe_subpath(&versions_path, Path::new(&mod_data.id))?;
 
-			fs::create_dir_all(&downloads_path)?;
+		files::join_safe_subpath(&mod_versions_path, Path::new(version_id))
+	}
 
-			let zip_path = downloads_path.join(format!("{}.zip", mod_id));
+	// Each version gets its own archive and extraction folder, so versions can be downloaded at the same time.
+	fn get_mod_download_paths(&self, mod_id: &str, version_id: &str) -> Result<(PathBuf, PathBuf)> {
+		let downloads_path = files::join_safe_subpath(
+			&paths::installed_mods_path()?
+				.join(&self.get_data().id)
+				.join("downloads"),
+			Path::new(mod_id),
+		)?;
+
+		Ok((
+			files::join_safe_subpath(&downloads_path, Path::new(&format!("{version_id}.zip")))?,
+			files::join_safe_subpath(&downloads_path, Path::new(version_id))?,
+		))
+	}
 
-			// TODO Stream to disk instead of keeping it all in memory.
-			fs::write(&zip_path, response.bytes().await?)?;
-			let file = File::open(&zip_path)?;
+	// The main mod folder always has the version that was downloaded most recently.
+	// Any other downloaded versions are kept in their own separate folders.
+	fn get_local_mod_version(
+		&self,
+		mod_data: &CommonModData,
+		version_id: &str,
+	) -> Result<LocalMod> {
+		let main_path = self.get_mod_path(mod_data)?;
 
-			let mut zip_archive = ZipArchive::new(file)?;
+		let is_main_version = mod_manifest::get(&local_mod::get_manifest_path(&main_path))
+			.is_some_and(|manifest| manifest.version == version_id);
 
-			if let Some(root) = &latest_version.root {
-				let unzip_path = downloads_path.join(mod_id);
-				zip_archive.extract(downloads_path.join(mod_id))?;
-				files::copy_dir_all(unzip_path.join(root), &target_path)?;
-			} else {
-				zip_archive.extract(&target_path)?;
-			}
+		let path = if is_main_version {
+			main_path
+		} else {
+			self.get_mod_version_path(mod_data, version_id)?
+		};
+
+		if !path.is_dir() {
+			return Err(Error::LocalModVersionNotFound(
+				mod_data.id.clone(),
+				version_id.to_string(),
+			));
+		}
+
+		Ok(LocalMod::from_common_data(mod_data, &path))
+	}
 
-			// Saves the manifest so we know which version of the mod we installed.
-			fs::write(
-				local_mod::get_manifest_path(&target_path),
-				serde_json::to_string_pretty(&mod_manifest::Manifest {
-					version: latest_version.id.clone(),
-					runnable: latest_version.runnable.clone(),
-					engine: remote_mod.common.engine,
-					unity_backend: remote_mod.common.unity_backend,
-				})?,
-			)?;
+	// Before the main mod folder gets replaced by a new download,
+	// we move the old version away to its own folder so games can keep using it.
+	fn archive_main_mod_version(&self, mod_data: &CommonModData) -> Result {
+		let main_path = self.get_mod_path(mod_data)?;
 
+		if !main_path.is_dir() {
 			return Ok(());
 		}
-		Err(Error::ModDownloadNotAvailable(remote_mod.common.id.clone()))
+
+		if let Some(manifest) = mod_manifest::get(&local_mod::get_manifest_path(&main_path)) {
+			let version_path = self.get_mod_version_path(mod_data, &manifest.version)?;
+
+			if !version_path.is_dir() {
+				fs::create_dir_all(paths::path_parent(&version_path)?)?;
+				fs::rename(&main_path, &version_path)?;
+				return Ok(());
+			}
+		}
+
+		fs::remove_dir_all(&main_path)?;
+
+		Ok(())
+	}
+
+	// If no version is given, the latest version gets downloaded into the main mod folder.
+	async fn download_mod<F>(
+		&self,
+		remote_mod: &RemoteMod,
+		version_id: Option<&str>,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let mod_id = &remote_mod.common.id;
+
+		let latest_version = remote_mod
+			.data
+			.latest_version
+			.as_ref()
+			.ok_or_else(|| Error::ModDownloadNotAvailable(mod_id.clone()))?;
+
+		let version = match version_id {
+			Some(version_id) => remote_mod.data.get_version(version_id).ok_or_else(|| {
+				Error::ModVersionNotAvailable(mod_id.clone(), version_id.to_string())
+			})?,
+			None => latest_version,
+		};
+
+		let is_latest_version = version.id == latest_version.id;
+
+		let target_path = if is_latest_version {
+			self.get_mod_path(&remote_mod.common)?
+		} else {
+			self.get_mod_version_path(&remote_mod.common, &version.id)?
+		};
+
+		let (zip_path, unzip_path) = self.get_mod_download_paths(mod_id, &version.id)?;
+		fs::create_dir_all(paths::path_parent(&zip_path)?)?;
+
+		downloads::download_file(
+			&version.url,
+			&zip_path,
+			version.sha256.as_deref(),
+			|current, total| {
+				on_progress(ModProgress::new(
+					mod_id,
+					ProgressPhase::Download,
+					current,
+					total,
+				));
+			},
+		)
+		.await?;
+
+		if !remote_mod.data.signing_keys.is_empty() {
+			let signature = version
+				.signature
+				.as_deref()
+				.ok_or_else(|| Error::SignatureMissing(version.url.clone()))?;
+
+			if let Err(error) =
+				signatures::verify_file(&zip_path, signature, &remote_mod.data.signing_keys)
+			{
+				// Don't leave an untrusted file around for something else to pick up.
+				fs::remove_file(&zip_path)?;
+				return Err(error);
+			}
+		}
+
+		if is_latest_version {
+			self.archive_main_mod_version(&remote_mod.common)?;
+		} else if target_path.is_dir() {
+			fs::remove_dir_all(&target_path)?;
+		}
+
+		// Archives with a root folder get extracted somewhere else first, and then only the root gets copied.
+		let unzip_path = if version.root.is_some() {
+			unzip_path
+		} else {
+			target_path.clone()
+		};
+		if version.root.is_some() && unzip_path.is_dir() {
+			fs::remove_dir_all(&unzip_path)?;
+		}
+
+		files::extract_zip_with(
+			&zip_path,
+			&unzip_path,
+			|entries| {
+				on_progress(ModProgress::extract_entries(mod_id, entries));
+				Ok(())
+			},
+			|current, total| {
+				on_progress(ModProgress::new(
+					mod_id,
+					ProgressPhase::Extract,
+					current,
+					Some(total),
+				));
+			},
+		)?;
+
+		if let Some(root) = &version.root {
+			on_progress(ModProgress::new(mod_id, ProgressPhase::Copy, 0, Some(1)));
+			copy_archive_root(&unzip_path, root, &target_path)?;
+		}
+
+		// Saves the manifest so we know which version of the mod we installed.
+		fs::write(
+			local_mod::get_manifest_path(&target_path),
+			serde_json::to_string_pretty(&mod_manifest::Manifest {
+				version: version.id.clone(),
+				runnable: version.runnable.clone(),
+				engine: remote_mod.common.engine,
+				unity_backend: remote_mod.common.unity_backend,
+				pinned: false,
+				disabled: false,
+				dependencies: remote_mod.data.dependencies.clone(),
+				conflicts: remote_mod.data.conflicts.clone(),
+				loader_id: None,
+			})?,
+		)?;
+
+		on_progress(ModProgress::finished(mod_id));
+
+		Ok(())
 	}
 }
 
@@ -217,11 +527,219 @@ pub async fn get_map(resources_path: &Path) -> Map {
 	let mut map = Map::new();
 
 	add_entry::<BepInEx>(resources_path, &mut map).await;
+	add_entry::<GodotLoader>(resources_path, &mut map).await;
+	add_entry::<MelonLoader>(resources_path, &mut map).await;
 	add_entry::<RunnableLoader>(resources_path, &mut map).await;
+	add_entry::<UnrealLoader>(resources_path, &mut map).await;
 
 	map
 }
 
+// Copies the root folder of an extracted mod archive to where the mod should be.
+fn copy_archive_root(unzip_path: &Path, root: &Path, target_path: &Path) -> Result {
+	// The root comes from the remote database, so it can't be trusted to stay inside the archive.
+	let root_path = files::join_safe_subpath(unzip_path, root)?;
+	if !root_path.is_dir() {
+		return Err(Error::UnsafePath(
+			root.to_path_buf(),
+			"root folder doesn't exist in the archive".to_string(),
+		));
+	}
+
+	files::copy_dir_all(root_path, target_path)
+}
+
+// Some loaders need mod files to be placed in shared folders in the game directory,
+// so we need to keep track of which files belong to each mod to be able to remove them later.
+fn get_installed_files_list_path(
+	game: &InstalledGame,
+	loader_id: &str,
+	mod_id: &str,
+) -> Result<PathBuf> {
+	Ok(game
+		.get_installed_mods_folder()?
+		.join("installed-files")
+		.join(loader_id)
+		.join(format!("{mod_id}.json")))
+}
+
+// Loaders that don't have their own way of disabling things
+// can have files renamed to something they won't recognize.
+const DISABLED_SUFFIX: &str = ".disabled";
+
+pub fn get_disabled_path(path: &Path) -> PathBuf {
+	let mut disabled_path = path.as_os_str().to_owned();
+	disabled_path.push(DISABLED_SUFFIX);
+	PathBuf::from(disabled_path)
+}
+
+// Opposite of `get_disabled_path`. Paths that aren't disabled are returned as they are.
+pub fn get_enabled_path(path: &Path) -> PathBuf {
+	path.to_str()
+		.and_then(|path_text| path_text.strip_suffix(DISABLED_SUFFIX))
+		.map_or_else(|| path.to_path_buf(), PathBuf::from)
+}
+
+pub fn set_file_enabled(journal: &mut InstallJournal, path: &Path, enabled: bool) -> Result {
+	let disabled_path = get_disabled_path(path);
+
+	if enabled && disabled_path.is_file() {
+		journal.rename_file(&disabled_path, path)?;
+	} else if !enabled && path.is_file() {
+		journal.rename_file(path, &disabled_path)?;
+	}
+
+	Ok(())
+}
+
+fn read_installed_files_list(
+	game: &InstalledGame,
+	loader_id: &str,
+	mod_id: &str,
+) -> Result<Vec<PathBuf>> {
+	let installed_files_list_path = get_installed_files_list_path(game, loader_id, mod_id)?;
+
+	if !installed_files_list_path.is_file() {
+		return Ok(Vec::new());
+	}
+
+	Ok(serde_json::from_str(&fs::read_to_string(
+		&installed_files_list_path,
+	)?)?)
+}
+
+pub fn set_installed_files_enabled(
+	game: &InstalledGame,
+	journal: &mut InstallJournal,
+	loader_id: &str,
+	mod_id: &str,
+	enabled: bool,
+) -> Result {
+	for installed_file in read_installed_files_list(game, loader_id, mod_id)? {
+		set_file_enabled(journal, &installed_file, enabled)?;
+	}
+
+	Ok(())
+}
+
+pub fn write_installed_files_list(
+	game: &InstalledGame,
+	journal: &mut InstallJournal,
+	loader_id: &str,
+	mod_id: &str,
+	installed_files: &[PathBuf],
+) -> Result {
+	journal.write_file(
+		&get_installed_files_list_path(game, loader_id, mod_id)?,
+		serde_json::to_string_pretty(installed_files)?,
+	)
+}
+
+pub fn remove_installed_files(game: &InstalledGame, loader_id: &str, mod_id: &str) -> Result {
+	let mut journal = game.open_install_journal()?;
+
+	for installed_file in read_installed_files_list(game, loader_id, mod_id)? {
+		journal.remove_file(&installed_file)?;
+		journal.remove_file(&get_disabled_path(&installed_file))?;
+	}
+
+	journal.remove_file(&get_installed_files_list_path(game, loader_id, mod_id)?)
+}
+
+pub fn remove_installed_files_lists(
+	journal: &mut InstallJournal,
+	game: &InstalledGame,
+	loader_id: &str,
+) -> Result {
+	journal.remove_recorded_in(
+		&game
+			.get_installed_mods_folder()?
+			.join("installed-files")
+			.join(loader_id),
+	)
+}
+
+// Each folder in the installed mods path is a mod for the given engine.
+pub fn find_engine_mods(
+	loader_id: &str,
+	installed_mods_path: &Path,
+	engine: GameEngineBrand,
+) -> Result<HashMap<String, LocalMod>> {
+	Ok(paths::glob_path(&installed_mods_path.join("*"))?
+		.filter_map(|entry| match entry {
+			Ok(mod_path) if mod_path.is_dir() => {
+				match LocalMod::new(loader_id, &mod_path, Some(engine), None) {
+					Ok(local_mod) => Some((local_mod.common.id.clone(), local_mod)),
+					Err(error) => {
+						error!(
+							"Failed to create local mod from {}. Error: {}",
+							mod_path.display(),
+							error
+						);
+						None
+					}
+				}
+			}
+			Ok(_) => None,
+			Err(error) => {
+				error!(
+					"Failed to read mod from {}. Error: {}",
+					installed_mods_path.display(),
+					error
+				);
+				None
+			}
+		})
+		.collect())
+}
+
+// Unity mods are stored in a separate folder for each scripting backend.
+pub fn find_unity_mods(
+	loader_id: &str,
+	installed_mods_path: &Path,
+) -> Result<HashMap<String, LocalMod>> {
+	let mut local_mods = find_unity_backend_mods(
+		loader_id,
+		installed_mods_path,
+		UnityScriptingBackend::Il2Cpp,
+	)?;
+	local_mods.extend(find_unity_backend_mods(
+		loader_id,
+		installed_mods_path,
+		UnityScriptingBackend::Mono,
+	)?);
+
+	Ok(local_mods)
+}
+
+fn find_unity_backend_mods(
+	loader_id: &str,
+	installed_mods_path: &Path,
+	scripting_backend: UnityScriptingBackend,
+) -> Result<HashMap<String, LocalMod>> {
+	let mods_folder_path = installed_mods_path.join(scripting_backend.to_string());
+
+	let entries: Vec<_> = paths::glob_path(&mods_folder_path.join("*"))?.collect();
+
+	Ok(entries
+		.iter()
+		.filter_map(|entry| {
+			entry.as_ref().map_or(None, |mod_path| {
+				if let Ok(local_mod) = LocalMod::new(
+					loader_id,
+					mod_path,
+					Some(GameEngineBrand::Unity),
+					Some(scripting_backend),
+				) {
+					Some((local_mod.common.id.clone(), local_mod))
+				} else {
+					None
+				}
+			})
+		})
+		.collect())
+}
+
 pub fn get_data_map(map: &Map) -> Result<DataMap> {
 	map.values()
 		.map(|mod_loader| {
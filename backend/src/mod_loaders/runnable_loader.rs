@@ -16,14 +16,21 @@ use super::mod_loader::{
 };
 use crate::{
 	game_mod::CommonModData,
+	game_process,
+	install_journal::InstallJournal,
 	installed_game::InstalledGame,
 	local_mod::{
 		self,
 		LocalMod,
 		ModKind,
 	},
+	mod_loaders::mod_database::RunnableArgsCondition,
 	mod_manifest,
-	paths::glob_path,
+	paths::{
+		self,
+		glob_path,
+	},
+	progress::ModProgress,
 	result::Error,
 	serializable_enum,
 	serializable_struct,
@@ -38,6 +45,13 @@ serializable_enum!(RunnableParameter {
 	ExecutableName,
 	ExecutablePath,
 	GameJson,
+	GameFolderPath,
+	InstalledModsPath,
+	EngineVersion,
+	Architecture,
+	ProviderGameId,
+	// Only available if the game is already running when the runnable starts.
+	GameProcessId,
 });
 
 #[async_trait]
@@ -80,7 +94,40 @@ fn replace_parameter_value<TValue: AsRef<str>, TGetValue: Fn() -> Result<TValue>
 	}
 }
 
-fn replace_parameters(argument: &str, game: &InstalledGame) -> String {
+fn missing_parameter_error(parameter: RunnableParameter, game: &InstalledGame) -> Error {
+	Error::ModInstallInfoInsufficient(parameter.to_string(), game.executable.path.clone())
+}
+
+// Checks if every part of the condition that's set matches the game.
+fn matches_condition(condition: &RunnableArgsCondition, game: &InstalledGame) -> bool {
+	let engine = game.executable.engine.as_ref();
+	let engine_version = engine.and_then(|engine| engine.version.as_ref());
+
+	let matches_engine = condition
+		.engine
+		.into_iter()
+		.all(|brand| engine.is_some_and(|engine| engine.brand == brand));
+	let matches_engine_version = condition
+		.engine_major_version
+		.into_iter()
+		.all(|major_version| engine_version.is_some_and(|version| version.major == major_version));
+	let matches_operating_system = condition
+		.operating_system
+		.into_iter()
+		.all(|operating_system| game.executable.operating_system == Some(operating_system));
+	let matches_architecture = condition
+		.architecture
+		.into_iter()
+		.all(|architecture| game.executable.architecture == Some(architecture));
+
+	matches_engine && matches_engine_version && matches_operating_system && matches_architecture
+}
+
+fn replace_parameters(
+	argument: &str,
+	game: &InstalledGame,
+	game_process_id: Option<u32>,
+) -> String {
 	let mut result = argument.to_string();
 
 	result = replace_parameter_value(&result, RunnableParameter::ExecutableName, || {
@@ -92,44 +139,139 @@ fn replace_parameters(argument: &str, game: &InstalledGame) -> String {
 	result = replace_parameter_value(&result, RunnableParameter::GameJson, || {
 		Ok(serde_json::to_string(&game)?)
 	});
+	result = replace_parameter_value(&result, RunnableParameter::GameFolderPath, || {
+		Ok(paths::path_parent(&game.executable.path)?.to_string_lossy())
+	});
+	result = replace_parameter_value(&result, RunnableParameter::InstalledModsPath, || {
+		Ok(game
+			.get_installed_mods_folder()?
+			.to_string_lossy()
+			.to_string())
+	});
+	result = replace_parameter_value(&result, RunnableParameter::EngineVersion, || {
+		game.executable
+			.engine
+			.as_ref()
+			.and_then(|engine| engine.version.as_ref())
+			.map(|version| version.display.clone())
+			.ok_or_else(|| missing_parameter_error(RunnableParameter::EngineVersion, game))
+	});
+	result = replace_parameter_value(&result, RunnableParameter::Architecture, || {
+		game.executable
+			.architecture
+			.map(|architecture| architecture.to_string())
+			.ok_or_else(|| missing_parameter_error(RunnableParameter::Architecture, game))
+	});
+	result = replace_parameter_value(&result, RunnableParameter::ProviderGameId, || {
+		game.provider_game_id
+			.as_ref()
+			.ok_or_else(|| missing_parameter_error(RunnableParameter::ProviderGameId, game))
+	});
+	result = replace_parameter_value(&result, RunnableParameter::GameProcessId, || {
+		game_process_id
+			.or_else(|| game_process::find_process_id(&game.executable.path))
+			.map(|process_id| process_id.to_string())
+			.ok_or_else(|| missing_parameter_error(RunnableParameter::GameProcessId, game))
+	});
 
 	result
 }
 
+// Command that starts the runnable, with all the parameters in its arguments replaced.
+// If the game process id isn't given, we look for a running process of the game.
+pub fn get_command(
+	game: &InstalledGame,
+	local_mod: &LocalMod,
+	game_process_id: Option<u32>,
+) -> Result<Command> {
+	let mod_folder = &local_mod.data.path;
+
+	let runnable = local_mod
+		.data
+		.manifest
+		.as_ref()
+		.and_then(|manifest| manifest.runnable.as_ref())
+		.ok_or_else(|| Error::RunnableManifestNotFound(local_mod.common.id.clone()))?;
+
+	let args: Vec<String> = runnable
+		.args
+		.iter()
+		.chain(
+			runnable
+				.conditional_args
+				.iter()
+				.filter(|conditional_args| matches_condition(&conditional_args.condition, game))
+				.flat_map(|conditional_args| conditional_args.args.iter()),
+		)
+		.map(|arg| replace_parameters(arg, game, game_process_id))
+		.collect();
+
+	let mut command = Command::new(mod_folder.join(&runnable.path));
+	command.current_dir(mod_folder).args(&args);
+
+	Ok(command)
+}
+
 #[async_trait]
 impl ModLoaderActions for RunnableLoader {
 	fn get_data(&self) -> &ModLoaderData {
 		&self.data
 	}
 
-	fn install(&self, _game: &InstalledGame) -> Result {
+	fn install<F>(
+		&self,
+		_game: &InstalledGame,
+		_journal: &mut InstallJournal,
+		_on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
 		todo!()
 	}
 
-	async fn install_mod_inner(&self, game: &InstalledGame, local_mod: &LocalMod) -> Result {
-		let mod_folder = self.get_mod_path(&local_mod.common)?;
-
-		let runnable = local_mod
-			.data
-			.manifest
-			.as_ref()
-			.and_then(|manifest| manifest.runnable.as_ref())
-			.ok_or_else(|| Error::RunnableManifestNotFound(local_mod.common.id.clone()))?;
+	fn uninstall(&self, _game: &InstalledGame) -> Result {
+		// Runnables don't install anything into the game.
+		Ok(())
+	}
 
-		let args: Vec<String> = runnable
-			.args
-			.iter()
-			.map(|arg| replace_parameters(arg, game))
-			.collect();
+	fn uninstall_mod_inner(&self, _game: &InstalledGame, _mod_id: &str) -> Result {
+		Ok(())
+	}
 
-		Command::new(mod_folder.join(&runnable.path))
-			.current_dir(mod_folder)
-			.args(&args)
-			.spawn()?;
+	fn set_enabled_inner(
+		&self,
+		_game: &InstalledGame,
+		_journal: &mut InstallJournal,
+		_enabled: bool,
+	) -> Result {
+		Ok(())
+	}
 
+	fn set_mod_enabled_inner(
+		&self,
+		_game: &InstalledGame,
+		_journal: &mut InstallJournal,
+		_mod_id: &str,
+		_enabled: bool,
+	) -> Result {
 		Ok(())
 	}
 
+	// Runnables only ever get started through runnable_process, so every process is supervised.
+	async fn install_mod_inner<F>(
+		&self,
+		_game: &InstalledGame,
+		local_mod: &LocalMod,
+		_journal: &mut InstallJournal,
+		_on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		Err(Error::RunnableNotInstallable(local_mod.common.id.clone()))
+	}
+
 	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf> {
 		Ok(Self::get_installed_mods_path()?.join(&mod_data.id))
 	}
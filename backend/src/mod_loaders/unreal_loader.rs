@@ -0,0 +1,268 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use async_trait::async_trait;
+
+use super::mod_loader::ModLoaderStatic;
+use crate::{
+	game_engines::game_engine::GameEngineBrand,
+	game_mod::CommonModData,
+	install_journal::InstallJournal,
+	installed_game::InstalledGame,
+	local_mod::{
+		LocalMod,
+		ModKind,
+	},
+	mod_loaders::mod_loader::{
+		self,
+		ModLoaderActions,
+		ModLoaderData,
+	},
+	paths,
+	progress::{
+		ModProgress,
+		ProgressPhase,
+	},
+	serializable_struct,
+	Error,
+	Result,
+};
+
+// Files that get added next to the shipping exe when installing UE4SS.
+const UE4SS_GAME_FOLDER_FILES: [&str; 1] = ["dwmapi.dll"];
+
+// Folder (relative to the shipping exe folder) where UE4SS keeps its own files.
+const UE4SS_FOLDER: &str = "ue4ss";
+
+// Folders inside a local mod that hold each type of Unreal mod.
+const MOD_PAKS_FOLDER: &str = "paks";
+const MOD_UE4SS_MODS_FOLDER: &str = "ue4ss-mods";
+
+serializable_struct!(UnrealLoader {
+	pub data: ModLoaderData,
+	pub id: &'static str,
+});
+
+#[async_trait]
+impl ModLoaderStatic for UnrealLoader {
+	const ID: &'static str = "unreal";
+
+	async fn new(resources_path: &Path) -> Result<Self> {
+		Ok(Self {
+			id: Self::ID,
+			data: ModLoaderData {
+				id: Self::ID.to_string(),
+				path: resources_path.join(Self::ID),
+				kind: ModKind::Installable,
+			},
+		})
+	}
+}
+
+// The shipping exe is usually at <Game>/<Project>/Binaries/Win64/<Project>-Win64-Shipping.exe,
+// and the paks are at <Game>/<Project>/Content/Paks.
+fn get_paks_folder(game: &InstalledGame) -> Result<PathBuf> {
+	let binaries_folder = paths::path_parent(paths::path_parent(&game.executable.path)?)?;
+
+	Ok(paths::path_parent(binaries_folder)?
+		.join("Content")
+		.join("Paks"))
+}
+
+impl UnrealLoader {
+	fn get_ue4ss_archive_path(&self, game: &InstalledGame) -> Result<PathBuf> {
+		let get_missing_info_error = |info: &str| {
+			Error::ModInstallInfoInsufficient(info.to_string(), game.executable.path.clone())
+		};
+
+		let engine_version = game
+			.executable
+			.engine
+			.as_ref()
+			.and_then(|engine| engine.version.as_ref())
+			.ok_or_else(|| get_missing_info_error("engine_version"))?;
+
+		Ok(self
+			.data
+			.path
+			.join("ue4ss")
+			.join(format!("UE{}", engine_version.major))
+			.join(
+				game.executable
+					.architecture
+					.ok_or_else(|| get_missing_info_error("architecture"))?
+					.to_string(),
+			)
+			.join("mod-loader.zip"))
+	}
+}
+
+#[async_trait]
+impl ModLoaderActions for UnrealLoader {
+	fn get_data(&self) -> &ModLoaderData {
+		&self.data
+	}
+
+	// Installs UE4SS. Mods that only have paks don't need it.
+	fn install<F>(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let ue4ss_archive = self.get_ue4ss_archive_path(game)?;
+		let exe_folder = paths::path_parent(&game.executable.path)?;
+
+		journal.extract_zip(
+			&ue4ss_archive,
+			exe_folder,
+			|entries| on_progress(ModProgress::extract_entries(Self::ID, entries)),
+			|current, total| {
+				on_progress(ModProgress::new(
+					Self::ID,
+					ProgressPhase::Extract,
+					current,
+					Some(total),
+				));
+			},
+		)?;
+
+		// Extracting the loader again would otherwise re-enable it.
+		if game.is_mod_loader_disabled(Self::ID) {
+			self.set_enabled_inner(game, journal, false)?;
+		}
+
+		Ok(())
+	}
+
+	async fn install_mod_inner<F>(
+		&self,
+		game: &InstalledGame,
+		local_mod: &LocalMod,
+		journal: &mut InstallJournal,
+		on_progress: F,
+	) -> Result
+	where
+		F: Fn(ModProgress) + Send + Sync,
+	{
+		let mod_id = &local_mod.common.id;
+		let mut installed_files = Vec::new();
+
+		let mod_ue4ss_mods_path = local_mod.data.path.join(MOD_UE4SS_MODS_FOLDER);
+		if mod_ue4ss_mods_path.is_dir() {
+			// Loader progress is reported as part of the mod that triggered the loader install.
+			self.install(game, journal, |progress| {
+				on_progress(ModProgress {
+					mod_id: mod_id.clone(),
+					..progress
+				});
+			})?;
+
+			on_progress(ModProgress::new(mod_id, ProgressPhase::Copy, 0, Some(2)));
+
+			let ue4ss_mods_folder = paths::path_parent(&game.executable.path)?
+				.join(UE4SS_FOLDER)
+				.join("Mods");
+
+			for entry in fs::read_dir(&mod_ue4ss_mods_path)? {
+				let entry = entry?;
+				if !entry.file_type()?.is_dir() {
+					continue;
+				}
+
+				let target_folder = ue4ss_mods_folder.join(entry.file_name());
+				installed_files.extend(journal.copy_dir_all(&entry.path(), &target_folder)?);
+
+				// UE4SS only loads mods that are enabled, either in mods.txt or with this file.
+				let enabled_file_path = target_folder.join("enabled.txt");
+				journal.write_file(&enabled_file_path, "")?;
+				installed_files.push(enabled_file_path);
+			}
+		}
+
+		on_progress(ModProgress::new(mod_id, ProgressPhase::Copy, 1, Some(2)));
+
+		let mod_paks_path = local_mod.data.path.join(MOD_PAKS_FOLDER);
+		if mod_paks_path.is_dir() {
+			installed_files.extend(journal.copy_dir_all(
+				&mod_paks_path,
+				&get_paks_folder(game)?.join("~mods").join(mod_id),
+			)?);
+		}
+
+		mod_loader::write_installed_files_list(game, journal, Self::ID, mod_id, &installed_files)
+	}
+
+	fn uninstall(&self, game: &InstalledGame) -> Result {
+		let mut journal = game.open_install_journal()?;
+		let exe_folder = paths::path_parent(&game.executable.path)?;
+
+		for file_name in UE4SS_GAME_FOLDER_FILES {
+			let file_path = exe_folder.join(file_name);
+			journal.remove_file(&file_path)?;
+			journal.remove_file(&mod_loader::get_disabled_path(&file_path))?;
+		}
+
+		// Logs and settings UE4SS generates at runtime are left alone.
+		journal.remove_recorded_in(&exe_folder.join(UE4SS_FOLDER))?;
+
+		// The mods themselves should have been uninstalled before this,
+		// so this only removes the ~mods folder if nothing else was left in there.
+		journal.remove_recorded_in(&get_paks_folder(game)?.join("~mods"))?;
+
+		mod_loader::remove_installed_files_lists(&mut journal, game, Self::ID)
+	}
+
+	fn uninstall_mod_inner(&self, game: &InstalledGame, mod_id: &str) -> Result {
+		mod_loader::remove_installed_files(game, Self::ID, mod_id)?;
+
+		// Each mod gets its own folder inside ~mods, which would be left empty otherwise.
+		game.open_install_journal()?
+			.remove_recorded_in(&get_paks_folder(game)?.join("~mods").join(mod_id))
+	}
+
+	// Only UE4SS can be disabled. Pak mods are loaded by the game itself.
+	fn set_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		enabled: bool,
+	) -> Result {
+		let exe_folder = paths::path_parent(&game.executable.path)?;
+
+		for file_name in UE4SS_GAME_FOLDER_FILES {
+			mod_loader::set_file_enabled(journal, &exe_folder.join(file_name), enabled)?;
+		}
+
+		Ok(())
+	}
+
+	fn set_mod_enabled_inner(
+		&self,
+		game: &InstalledGame,
+		journal: &mut InstallJournal,
+		mod_id: &str,
+		enabled: bool,
+	) -> Result {
+		mod_loader::set_installed_files_enabled(game, journal, Self::ID, mod_id, enabled)
+	}
+
+	fn get_mod_path(&self, mod_data: &CommonModData) -> Result<PathBuf> {
+		Ok(Self::get_installed_mods_path()?.join(&mod_data.id))
+	}
+
+	fn get_local_mods(&self) -> Result<HashMap<String, LocalMod>> {
+		let installed_mods_path = Self::get_installed_mods_path()?;
+
+		mod_loader::find_engine_mods(Self::ID, &installed_mods_path, GameEngineBrand::Unreal)
+	}
+}
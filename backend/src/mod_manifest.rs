@@ -19,6 +19,19 @@ serializable_struct!(Manifest {
 	pub runnable: Option<RunnableModData>,
 	pub engine: Option<GameEngineBrand>,
 	pub unity_backend: Option<UnityScriptingBackend>,
+	#[serde(default)]
+	pub dependencies: Vec<String>,
+	#[serde(default)]
+	pub conflicts: Vec<String>,
+	// Only used in the manifests copied to each game, to stop them from getting updated.
+	#[serde(default)]
+	pub pinned: bool,
+	#[serde(default)]
+	pub disabled: bool,
+	// Only set in the manifests copied to each game,
+	// so mods that aren't available anymore can still be uninstalled.
+	#[serde(default)]
+	pub loader_id: Option<String>,
 });
 
 pub fn get(path: &Path) -> Option<Manifest> {
@@ -0,0 +1,310 @@
+use std::{
+	collections::{
+		HashMap,
+		HashSet,
+	},
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use log::error;
+
+use crate::{
+	files,
+	game_mod,
+	installed_game::InstalledGame,
+	maps::TryGettable,
+	mod_loaders::mod_loader::{
+		self,
+		ModLoaderActions,
+	},
+	progress::ModProgress,
+	remote_mod,
+	serializable_struct,
+	Result,
+};
+
+// Snapshot of which mods are installed in a game, so the user can quickly switch between setups.
+serializable_struct!(ModProfile {
+	pub name: String,
+	// Maps mod id to the version that should be installed.
+	pub mod_versions: HashMap<String, String>,
+	pub pinned_mods: HashSet<String>,
+	pub disabled_mods: HashSet<String>,
+	pub disabled_mod_loaders: HashSet<String>,
+	// Contents of each loader's config files, by loader id and then file name.
+	#[serde(default)]
+	pub loader_configs: HashMap<String, HashMap<String, String>>,
+});
+
+serializable_struct!(ModProfiles {
+	// Profile that was last saved or switched to.
+	pub active: Option<String>,
+	pub profiles: HashMap<String, ModProfile>,
+});
+
+impl ModProfile {
+	// Reads the installed manifests from disk instead of the cached game fields,
+	// since those can be out of date while a profile is being applied.
+	pub fn from_game(
+		game: &InstalledGame,
+		name: &str,
+		mod_loaders: &mod_loader::Map,
+	) -> Result<Self> {
+		let mut profile = Self {
+			name: name.to_string(),
+			mod_versions: HashMap::new(),
+			pinned_mods: HashSet::new(),
+			disabled_mods: HashSet::new(),
+			disabled_mod_loaders: game.get_disabled_mod_loaders(),
+			loader_configs: read_loader_configs(game, mod_loaders)?,
+		};
+
+		for (mod_id, manifest) in game.get_installed_mod_manifests()? {
+			if manifest.pinned {
+				profile.pinned_mods.insert(mod_id.clone());
+			}
+			if manifest.disabled {
+				profile.disabled_mods.insert(mod_id.clone());
+			}
+			profile.mod_versions.insert(mod_id, manifest.version);
+		}
+
+		Ok(profile)
+	}
+}
+
+fn read_loader_configs(
+	game: &InstalledGame,
+	mod_loaders: &mod_loader::Map,
+) -> Result<HashMap<String, HashMap<String, String>>> {
+	let mut loader_configs = HashMap::new();
+
+	for (mod_loader_id, mod_loader) in mod_loaders {
+		let Some(config_folder) = mod_loader.get_config_folder(game)? else {
+			continue;
+		};
+		if !config_folder.is_dir() {
+			continue;
+		}
+
+		let mut config_files = HashMap::new();
+		for entry in fs::read_dir(config_folder)? {
+			let entry = entry?;
+			if !entry.file_type()?.is_file() {
+				continue;
+			}
+
+			// Anything that isn't text isn't a config file we know how to restore.
+			if let Ok(contents) = fs::read_to_string(entry.path()) {
+				config_files.insert(entry.file_name().to_string_lossy().to_string(), contents);
+			}
+		}
+
+		loader_configs.insert(mod_loader_id.clone(), config_files);
+	}
+
+	Ok(loader_configs)
+}
+
+// Config files are written after everything else, since installing a loader replaces some of them.
+// Config files the profile doesn't know about are left alone.
+fn write_loader_configs(
+	game: &InstalledGame,
+	mod_loaders: &mod_loader::Map,
+	loader_configs: &HashMap<String, HashMap<String, String>>,
+) -> Result {
+	let mut journal = game.open_install_journal()?;
+
+	for (mod_loader_id, config_files) in loader_configs {
+		let Some(config_folder) = mod_loaders
+			.try_get(mod_loader_id)?
+			.get_config_folder(game)?
+		else {
+			continue;
+		};
+		// The loader isn't installed in this profile.
+		if !config_folder.is_dir() {
+			continue;
+		}
+
+		for (file_name, contents) in config_files {
+			let config_path = files::join_safe_subpath(&config_folder, Path::new(file_name))?;
+			if fs::read_to_string(&config_path).ok().as_ref() != Some(contents) {
+				journal.write_file(&config_path, contents)?;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn get_path(game: &InstalledGame) -> Result<PathBuf> {
+	Ok(game.get_installed_mods_folder()?.join("mod-profiles.json"))
+}
+
+pub fn read(game: &InstalledGame) -> ModProfiles {
+	get_path(game)
+		.ok()
+		.and_then(|path| fs::read_to_string(path).ok())
+		.and_then(|contents| serde_json::from_str(&contents).ok())
+		.unwrap_or_else(|| ModProfiles {
+			active: None,
+			profiles: HashMap::new(),
+		})
+}
+
+pub fn write(game: &InstalledGame, mod_profiles: &ModProfiles) -> Result {
+	fs::write(get_path(game)?, serde_json::to_string_pretty(mod_profiles)?)?;
+
+	Ok(())
+}
+
+// Saves the mods currently installed in the game as a profile, replacing any profile with the same name.
+pub fn save(game: &InstalledGame, name: &str, mod_loaders: &mod_loader::Map) -> Result {
+	let mut mod_profiles = read(game);
+
+	mod_profiles.profiles.insert(
+		name.to_string(),
+		ModProfile::from_game(game, name, mod_loaders)?,
+	);
+	mod_profiles.active = Some(name.to_string());
+
+	write(game, &mod_profiles)
+}
+
+pub fn delete(game: &InstalledGame, name: &str) -> Result {
+	let mut mod_profiles = read(game);
+
+	mod_profiles.profiles.remove(name);
+	if mod_profiles.active.as_deref() == Some(name) {
+		mod_profiles.active = None;
+	}
+
+	write(game, &mod_profiles)
+}
+
+// Installs and uninstalls whatever is needed to get the game from its current state to the profile.
+// If anything fails, the game is switched back to how it was before.
+// Everything the switch wrote gets rolled back from a single journal checkpoint,
+// and the previous profile is applied again to bring back anything that was uninstalled.
+pub async fn switch<F>(
+	game: &InstalledGame,
+	name: &str,
+	mod_loaders: &mod_loader::Map,
+	mod_data_map: &game_mod::CommonDataMap,
+	remote_mods: &remote_mod::Map,
+	on_progress: F,
+) -> Result
+where
+	F: Fn(ModProgress) + Send + Sync,
+{
+	let mut mod_profiles = read(game);
+	let target_profile = mod_profiles.profiles.try_get(name)?.clone();
+	let previous_profile = ModProfile::from_game(game, "", mod_loaders)?;
+	let checkpoint = game.open_install_journal()?.checkpoint();
+
+	if let Err(switch_error) = apply(
+		game,
+		&target_profile,
+		mod_loaders,
+		mod_data_map,
+		remote_mods,
+		&on_progress,
+	)
+	.await
+	{
+		error!("Failed to switch to mod profile `{name}`, restoring previous mods. Error: {switch_error}");
+
+		if let Err(rollback_error) = game.open_install_journal()?.rollback(checkpoint) {
+			error!("Failed to roll back changes after failing to switch profile. Error: {rollback_error}");
+		}
+
+		if let Err(restore_error) = apply(
+			game,
+			&previous_profile,
+			mod_loaders,
+			mod_data_map,
+			remote_mods,
+			&on_progress,
+		)
+		.await
+		{
+			error!("Failed to restore previous mods after failing to switch profile. Error: {restore_error}");
+		}
+
+		return Err(switch_error);
+	}
+
+	mod_profiles.active = Some(name.to_string());
+	write(game, &mod_profiles)
+}
+
+async fn apply<F>(
+	game: &InstalledGame,
+	target_profile: &ModProfile,
+	mod_loaders: &mod_loader::Map,
+	mod_data_map: &game_mod::CommonDataMap,
+	remote_mods: &remote_mod::Map,
+	on_progress: F,
+) -> Result
+where
+	F: Fn(ModProgress) + Send + Sync,
+{
+	let current_profile = ModProfile::from_game(game, "", mod_loaders)?;
+
+	// Mods with a different version get uninstalled too, since they'll be reinstalled below.
+	for (mod_id, version) in &current_profile.mod_versions {
+		if target_profile.mod_versions.get(mod_id) != Some(version) {
+			mod_loaders
+				.try_get(&game.get_mod_loader_id(mod_id, mod_data_map)?)?
+				.uninstall_mod(game, mod_id)?;
+		}
+	}
+
+	for (mod_id, version) in &target_profile.mod_versions {
+		let mod_data = mod_data_map.try_get(mod_id)?;
+		let mod_loader = mod_loaders.try_get(&mod_data.loader_id)?;
+
+		if current_profile.mod_versions.get(mod_id) != Some(version) {
+			let local_mod =
+				if let Ok(local_mod) = mod_loader.get_local_mod_version(mod_data, version) {
+					local_mod
+				} else {
+					mod_loader
+						.download_mod(remote_mods.try_get(mod_id)?, Some(version), &on_progress)
+						.await?;
+					mod_loader.get_local_mod_version(mod_data, version)?
+				};
+
+			mod_loader
+				.install_mod(game, &local_mod, &on_progress)
+				.await?;
+		}
+
+		let disabled = target_profile.disabled_mods.contains(mod_id);
+		if game
+			.get_installed_mod_manifest(mod_id)
+			.is_some_and(|manifest| manifest.disabled != disabled)
+		{
+			mod_loader.set_mod_enabled(game, mod_id, !disabled)?;
+		}
+
+		game.set_mod_pinned(mod_id, target_profile.pinned_mods.contains(mod_id))?;
+	}
+
+	for mod_loader_id in game
+		.get_disabled_mod_loaders()
+		.symmetric_difference(&target_profile.disabled_mod_loaders)
+	{
+		mod_loaders.try_get(mod_loader_id)?.set_enabled(
+			game,
+			!target_profile.disabled_mod_loaders.contains(mod_loader_id),
+		)?;
+	}
+
+	write_loader_configs(game, mod_loaders, &target_profile.loader_configs)
+}
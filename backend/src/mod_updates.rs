@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use log::error;
+
+use crate::{
+	installed_game::{
+		self,
+		InstalledGame,
+	},
+	mod_loaders::mod_loader::{
+		ModLoader,
+		ModLoaderActions,
+	},
+	progress::ModProgress,
+	remote_mod::{
+		self,
+		RemoteMod,
+	},
+	serializable_struct,
+	Error,
+	Result,
+};
+
+serializable_struct!(ModUpdate {
+	pub mod_id: String,
+	pub installed_version: String,
+	pub latest_version: String,
+});
+
+// Maps game id to the list of mod updates available for that game.
+pub type Map = HashMap<String, Vec<ModUpdate>>;
+
+pub fn get_game_updates(game: &InstalledGame, remote_mods: &remote_mod::Map) -> Vec<ModUpdate> {
+	game.installed_mod_versions
+		.iter()
+		.filter_map(|(mod_id, installed_version)| {
+			// Pinned mods stay on whatever version the user picked.
+			if game.pinned_mods.contains(mod_id) {
+				return None;
+			}
+
+			let installed_version = installed_version.as_ref()?;
+			let latest_version = remote_mods.get(mod_id)?.data.latest_version.as_ref()?;
+
+			if &latest_version.id == installed_version {
+				return None;
+			}
+
+			Some(ModUpdate {
+				mod_id: mod_id.clone(),
+				installed_version: installed_version.clone(),
+				latest_version: latest_version.id.clone(),
+			})
+		})
+		.collect()
+}
+
+pub fn get_map(installed_games: &installed_game::Map, remote_mods: &remote_mod::Map) -> Map {
+	installed_games
+		.values()
+		.filter_map(|game| {
+			let updates = get_game_updates(game, remote_mods);
+
+			if updates.is_empty() {
+				None
+			} else {
+				Some((game.id.clone(), updates))
+			}
+		})
+		.collect()
+}
+
+pub async fn update_mod<F>(
+	game: &InstalledGame,
+	mod_loader: &ModLoader,
+	remote_mod: &RemoteMod,
+	on_progress: F,
+) -> Result
+where
+	F: Fn(ModProgress) + Send + Sync,
+{
+	let mod_id = &remote_mod.common.id;
+
+	let installed_version = game
+		.get_installed_mod_version(mod_id)
+		.ok_or_else(|| Error::ModNotInstalled(mod_id.clone()))?;
+
+	let latest_version = remote_mod
+		.data
+		.latest_version
+		.as_ref()
+		.ok_or_else(|| Error::ModDownloadNotAvailable(mod_id.clone()))?;
+
+	// Downloading the latest version moves the current local copy into its own version folder,
+	// so the old version is still around if anything goes wrong from here on.
+	let new_local_mod = if let Ok(local_mod) =
+		mod_loader.get_local_mod_version(&remote_mod.common, &latest_version.id)
+	{
+		local_mod
+	} else {
+		mod_loader
+			.download_mod(remote_mod, None, &on_progress)
+			.await?;
+		mod_loader.get_local_mod_version(&remote_mod.common, &latest_version.id)?
+	};
+
+	mod_loader.uninstall_mod(game, mod_id)?;
+
+	if let Err(install_error) = mod_loader
+		.install_mod(game, &new_local_mod, &on_progress)
+		.await
+	{
+		error!("Failed to install update for mod `{mod_id}`, restoring version `{installed_version}`. Error: {install_error}");
+
+		mod_loader.uninstall_mod(game, mod_id)?;
+
+		let old_local_mod =
+			mod_loader.get_local_mod_version(&remote_mod.common, &installed_version)?;
+
+		mod_loader
+			.install_mod(game, &old_local_mod, &on_progress)
+			.await?;
+
+		return Err(install_error);
+	}
+
+	Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{
+	files::ArchiveEntry,
+	serializable_enum,
+	serializable_struct,
+};
+
+serializable_enum!(ProgressPhase {
+	Download,
+	Extract,
+	Copy,
+	WaitForGame,
+	Finished,
+});
+
+// For downloads, `current` and `total` are in bytes.
+// For WaitForGame, they're in seconds.
+// For the other phases, they're the number of files or steps.
+serializable_struct!(ModProgress {
+	pub mod_id: String,
+	pub phase: ProgressPhase,
+	pub current: u64,
+	pub total: Option<u64>,
+	// Everything an archive is about to write, sent once before extracting. Empty otherwise.
+	// This is only informational, extraction doesn't wait for the frontend to see it.
+	pub entries: Vec<ArchiveEntry>,
+});
+
+impl ModProgress {
+	pub fn new(mod_id: &str, phase: ProgressPhase, current: u64, total: Option<u64>) -> Self {
+		Self {
+			mod_id: mod_id.to_string(),
+			phase,
+			current,
+			total,
+			entries: Vec::new(),
+		}
+	}
+
+	pub fn extract_entries(mod_id: &str, entries: &[ArchiveEntry]) -> Self {
+		let total = u64::try_from(entries.len()).ok();
+
+		Self {
+			entries: entries.to_vec(),
+			..Self::new(mod_id, ProgressPhase::Extract, 0, total)
+		}
+	}
+
+	pub fn finished(mod_id: &str) -> Self {
+		Self::new(mod_id, ProgressPhase::Finished, 1, Some(1))
+	}
+}
@@ -110,6 +110,7 @@ impl ProviderActions for Steam {
 									let app_id_string = app.app_id.to_string();
 
 									game.set_provider_game_id(&app_id_string);
+									game.set_steam_app_id(app.app_id);
 									game.set_thumbnail_url(&get_steam_thumbnail(&app_id_string));
 									game.set_start_command_string(&get_start_command(
 										&launch_option,
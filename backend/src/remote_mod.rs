@@ -12,6 +12,16 @@ serializable_struct!(RemoteModData {
   pub source_code: String,
   pub description: String,
   pub latest_version: Option<ModDownload>,
+  pub versions: Vec<ModDownload>,
+  pub dependencies: Vec<String>,
+  pub conflicts: Vec<String>,
+  // Id of the database source this mod came from.
+  pub source_id: String,
+  // Public keys that downloads for this mod need to be signed with. Empty if the source isn't signed.
+  pub signing_keys: Vec<String>,
+  // Set when the mod database couldn't be reached, so this came from a cached copy.
+  // Unix timestamp, in seconds, of when that copy was last up to date.
+  pub database_cached_at: Option<i64>,
 });
 
 serializable_struct!(RemoteMod {
@@ -19,4 +29,13 @@ serializable_struct!(RemoteMod {
 	pub data: RemoteModData,
 });
 
+impl RemoteModData {
+	pub fn get_version(&self, version_id: &str) -> Option<&ModDownload> {
+		self.latest_version
+			.iter()
+			.chain(self.versions.iter())
+			.find(|version| version.id == version_id)
+	}
+}
+
 pub type Map = HashMap<String, RemoteMod>;
@@ -89,15 +89,109 @@ pub enum Error {
 	#[error("Data entry not found: `{0}`")]
 	DataEntryNotFound(String),
 
+	#[error("Mods can't be installed in games made with `{0}` version `{1}`")]
+	EngineVersionNotSupported(String, String),
+
 	#[error("Unity backend not known for mod `{0}`")]
 	UnityBackendUnknown(String),
 
 	#[error("Download not available for mod `{0}`")]
 	ModDownloadNotAvailable(String),
 
+	#[error("Version `{1}` isn't available for mod `{0}`")]
+	ModVersionNotAvailable(String, String),
+
+	#[error("Version `{1}` of mod `{0}` hasn't been downloaded")]
+	LocalModVersionNotFound(String, String),
+
+	#[error("Mod `{0}` isn't installed in this game")]
+	ModNotInstalled(String),
+
+	#[error("Mod `{0}` is required by `{1}`, but it isn't available")]
+	ModDependencyNotFound(String, String),
+
+	#[error("Mods depend on each other in a loop: {0}")]
+	ModDependencyCycle(String),
+
+	#[error("Mod `{0}` can't be installed together with mod `{1}`")]
+	ModConflict(String, String),
+
+	#[error("Mod `{0}` needs to be installed for mod `{1}`, but that wasn't confirmed")]
+	ModDependencyNotApproved(String, String),
+
+	#[error("Couldn't figure out what kind of mod this is: `{0}`")]
+	ModImportUnrecognized(PathBuf),
+
+	#[error(
+		"Found more than one executable in `{0}`, so it's not clear which one runs the mod: {1}"
+	)]
+	ModImportExecutableAmbiguous(PathBuf, String),
+
+	#[error("A mod with id `{0}` already exists")]
+	ModAlreadyExists(String),
+
+	#[error("Mod `{0}` was made for {1} games, but this game uses {2}")]
+	ModUnityBackendMismatch(String, String, String),
+
+	#[error("Invalid value `{1}` for config entry `{0}`: {2}")]
+	ConfigValueInvalid(String, String, String),
+
+	#[error("Downloaded file from `{0}` is corrupted. Expected SHA-256 `{1}`, got `{2}`")]
+	DownloadChecksumMismatch(String, String, String),
+
 	#[error("Operation can't be completed without a `runnable` section in the mod manifest (rai-pal-manifest.json) `{0}`")]
 	RunnableManifestNotFound(String),
 
+	#[error("Mod `{0}` is a runnable, so it can only be started, not installed.")]
+	RunnableNotInstallable(String),
+
+	#[error("Failed to find the Wine prefix for this game. **Try running the game once through Steam**. (Looked in `{0}`)")]
+	WinePrefixNotFound(PathBuf),
+
+	#[error("Failed to parse Wine registry file: {0}")]
+	WineRegistryInvalid(String),
+
+	#[error("Mod loader `{0}` doesn't support this game's platform yet ({1})")]
+	ModLoaderPlatformUnsupported(String, String),
+
+	#[error("Failed to find launch script `{0}`. **Try reinstalling the mod loader**.")]
+	LaunchScriptNotFound(PathBuf),
+
+	#[error("Gave up waiting for the game to start after {1} seconds. Couldn't find a running process for `{0}`")]
+	GameProcessNotFound(PathBuf, u64),
+
+	#[error("Invalid database source `{0}`: {1}")]
+	InvalidDatabaseSource(String, String),
+
+	#[error("Found {2} issues in the {1} mod database from source `{0}`. Some mods might be missing. Check the logs for details.")]
+	ModDatabaseIssues(String, String, usize),
+
+	#[error("Invalid public key `{0}`: {1}")]
+	PublicKeyInvalid(String, String),
+
+	#[error("Signature verification failed: {0}")]
+	SignatureInvalid(String),
+
+	#[error("Missing signature for `{0}`, which comes from a source that requires signatures.")]
+	SignatureMissing(String),
+
+	#[error(
+		"Mod database source `{0}` doesn't have any public keys, but signatures are required."
+	)]
+	DatabaseSourceNotSigned(String),
+
+	#[error("Refusing to extract `{1}` from archive `{0}`: {2}")]
+	UnsafeArchiveEntry(PathBuf, String, String),
+
+	#[error("Refusing to extract archive `{0}`, since it would take more than {1} bytes")]
+	ArchiveTooBig(PathBuf, u64),
+
+	#[error("Unsafe path `{0}`: {1}")]
+	UnsafePath(PathBuf, String),
+
+	#[error("Invalid url `{0}`: {1}")]
+	InvalidUrl(String, String),
+
 	#[error("Can't run command because it isn't defined for this game.")]
 	CommandNotDefined(),
 }
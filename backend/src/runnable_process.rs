@@ -0,0 +1,183 @@
+use std::{
+	collections::HashMap,
+	fs::{
+		self,
+		File,
+	},
+	path::PathBuf,
+	process::{
+		Child,
+		Stdio,
+	},
+	sync::Mutex,
+	thread,
+	time::Duration,
+};
+
+use log::error;
+use tauri::AppHandle;
+
+use crate::{
+	app_state::StatefulHandle,
+	events::{
+		AppEvent,
+		EventEmitter,
+	},
+	installed_game::InstalledGame,
+	local_mod::LocalMod,
+	mod_loaders::runnable_loader,
+	paths,
+	serializable_struct,
+	Error,
+	Result,
+};
+
+// How often we check if a runnable process has exited.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+serializable_struct!(RunningRunnable {
+	pub pid: u32,
+	pub mod_id: String,
+	pub game_id: String,
+	// Unix timestamp, in seconds.
+	pub start_time: i64,
+	// File where the process stdout and stderr get written to.
+	pub log_path: PathBuf,
+});
+
+serializable_struct!(RunnableExit {
+	pub runnable: RunningRunnable,
+	// Missing if the process was killed by a signal.
+	pub exit_code: Option<i32>,
+});
+
+struct RunnableProcess {
+	runnable: RunningRunnable,
+	child: Child,
+}
+
+// Child processes can't be cloned, so this doesn't use the same Mutex<Option<Map>> as the rest of the state.
+#[derive(Default)]
+pub struct RunnableProcesses(Mutex<HashMap<u32, RunnableProcess>>);
+
+impl RunnableProcesses {
+	fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<u32, RunnableProcess>>> {
+		self.0
+			.lock()
+			.map_err(|err| Error::FailedToAccessStateData(err.to_string()))
+	}
+
+	pub fn get_running(&self) -> Result<Vec<RunningRunnable>> {
+		let mut running: Vec<_> = self
+			.lock()?
+			.values()
+			.map(|process| process.runnable.clone())
+			.collect();
+
+		running.sort_by_key(|runnable| runnable.start_time);
+
+		Ok(running)
+	}
+
+	// The exit still gets reported by the thread that's waiting for the process.
+	pub fn stop(&self, pid: u32) -> Result {
+		self.lock()?
+			.get_mut(&pid)
+			.ok_or_else(|| Error::DataEntryNotFound(pid.to_string()))?
+			.child
+			.kill()?;
+
+		Ok(())
+	}
+
+	// Returns the exit status if the process isn't running anymore, and stops tracking it.
+	fn try_remove_exited(&self, pid: u32) -> Result<Option<RunnableExit>> {
+		let mut processes = self.lock()?;
+
+		let process = processes
+			.get_mut(&pid)
+			.ok_or_else(|| Error::DataEntryNotFound(pid.to_string()))?;
+
+		let exit_code = match process.child.try_wait() {
+			Ok(None) => return Ok(None),
+			Ok(Some(status)) => status.code(),
+			Err(err) => {
+				error!("Failed to get status of runnable process {pid}. Error: {err}");
+				None
+			}
+		};
+
+		Ok(processes.remove(&pid).map(|exited_process| RunnableExit {
+			runnable: exited_process.runnable,
+			exit_code,
+		}))
+	}
+}
+
+fn get_log_path(mod_id: &str, start_time: i64) -> Result<PathBuf> {
+	let runnable_logs_path = paths::logs_path()?.join("runnables");
+	fs::create_dir_all(&runnable_logs_path)?;
+
+	Ok(runnable_logs_path.join(format!("{mod_id}-{start_time}.log")))
+}
+
+// Starts the runnable with its output going to a log file, and reports back when it exits.
+pub fn start(
+	handle: &AppHandle,
+	game: &InstalledGame,
+	local_mod: &LocalMod,
+	game_process_id: Option<u32>,
+) -> Result<RunningRunnable> {
+	let mod_id = &local_mod.common.id;
+	let start_time = chrono::Utc::now().timestamp();
+	let log_path = get_log_path(mod_id, start_time)?;
+	let log_file = File::create(&log_path)?;
+
+	let child = runnable_loader::get_command(game, local_mod, game_process_id)?
+		.stdin(Stdio::null())
+		.stdout(log_file.try_clone()?)
+		.stderr(log_file)
+		.spawn()?;
+
+	let runnable = RunningRunnable {
+		pid: child.id(),
+		mod_id: mod_id.clone(),
+		game_id: game.id.clone(),
+		start_time,
+		log_path,
+	};
+
+	handle.app_state().runnable_processes.lock()?.insert(
+		runnable.pid,
+		RunnableProcess {
+			runnable: runnable.clone(),
+			child,
+		},
+	);
+	handle.emit_event(AppEvent::SyncRunningRunnables, ());
+
+	let pid = runnable.pid;
+	let thread_handle = handle.clone();
+	thread::spawn(move || loop {
+		thread::sleep(EXIT_POLL_INTERVAL);
+
+		match thread_handle
+			.app_state()
+			.runnable_processes
+			.try_remove_exited(pid)
+		{
+			Ok(Some(runnable_exit)) => {
+				thread_handle.emit_event(AppEvent::RunnableExited, runnable_exit);
+				thread_handle.emit_event(AppEvent::SyncRunningRunnables, ());
+				break;
+			}
+			Ok(None) => {}
+			Err(err) => {
+				error!("Stopped waiting for runnable process {pid}. Error: {err}");
+				break;
+			}
+		}
+	});
+
+	Ok(runnable)
+}
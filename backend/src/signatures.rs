@@ -0,0 +1,84 @@
+use std::{
+	fs,
+	path::Path,
+};
+
+use log::error;
+use minisign_verify::{
+	PublicKey,
+	Signature,
+};
+
+use crate::{
+	paths::glob_path,
+	Error,
+	Result,
+};
+
+// Public keys in here are trusted for the official mod database.
+const TRUSTED_KEYS_FOLDER: &str = "trusted-keys";
+
+// Extension of the signature files that go next to signed databases.
+pub const SIGNATURE_EXTENSION: &str = "minisig";
+
+// Keys are stored as the base64 line of a minisign .pub file.
+pub fn parse_public_key(public_key: &str) -> Result<PublicKey> {
+	PublicKey::from_base64(public_key.trim())
+		.map_err(|error| Error::PublicKeyInvalid(public_key.to_string(), error.to_string()))
+}
+
+pub fn parse_signature(signature: &str) -> Result<Signature> {
+	Signature::decode(signature).map_err(|error| Error::SignatureInvalid(error.to_string()))
+}
+
+// Passes if the signature was made by any of the given keys.
+// `signed_name` is only used for the error message.
+pub fn verify(data: &[u8], signature: &str, public_keys: &[String], signed_name: &str) -> Result {
+	let decoded_signature = parse_signature(signature)?;
+
+	for public_key in public_keys {
+		match parse_public_key(public_key) {
+			Ok(decoded_key) => {
+				if decoded_key.verify(data, &decoded_signature, false).is_ok() {
+					return Ok(());
+				}
+			}
+			Err(error) => error!("Skipping invalid public key. Error: {error}"),
+		}
+	}
+
+	Err(Error::SignatureInvalid(format!(
+		"`{signed_name}` isn't signed by any of the trusted keys"
+	)))
+}
+
+pub fn verify_file(path: &Path, signature: &str, public_keys: &[String]) -> Result {
+	verify(
+		&fs::read(path)?,
+		signature,
+		public_keys,
+		&path.to_string_lossy(),
+	)
+}
+
+// Reads the minisign .pub files bundled with the app.
+pub fn read_bundled_keys(resources_path: &Path) -> Result<Vec<String>> {
+	let mut public_keys = Vec::new();
+
+	for key_path in glob_path(&resources_path.join(TRUSTED_KEYS_FOLDER).join("*.pub"))? {
+		let key_text = fs::read_to_string(key_path?)?;
+		PublicKey::decode(&key_text)
+			.map_err(|error| Error::PublicKeyInvalid(key_text.clone(), error.to_string()))?;
+
+		// The first line of a .pub file is a comment, the key is the last line.
+		if let Some(public_key) = key_text
+			.lines()
+			.map(str::trim)
+			.rfind(|line| !line.is_empty())
+		{
+			public_keys.push(public_key.to_string());
+		}
+	}
+
+	Ok(public_keys)
+}
@@ -9,14 +9,16 @@ use std::{
 use log::error;
 
 use crate::{
+	database_sources::{
+		self,
+		DatabaseSources,
+	},
 	game_engines::game_engine::GameEngineBrand,
 	serializable_enum,
 	serializable_struct,
 	Result,
 };
 
-const STEAM_APP_IDS_URL_BASE: &str = "https://raicuparta.github.io/rai-pal-db/steam-ids";
-
 serializable_enum!(UevrScore { A, B, C, D, E });
 
 serializable_struct!(SteamGame {
@@ -25,20 +27,14 @@ serializable_struct!(SteamGame {
 	pub uevr_score: Option<UevrScore>,
 });
 
-async fn get_list(list_name: &str) -> HashSet<String> {
-	match reqwest::get(format!("{STEAM_APP_IDS_URL_BASE}/{list_name}")).await {
-		Ok(response) => match response.text().await {
-			Ok(text) => text
-				.split('\n')
-				.map(|line| line.trim().to_string())
-				.collect(),
-			Err(err) => {
-				error!("Failed to parse ids list {list_name}: {err}");
-				HashSet::default()
-			}
-		},
+async fn get_list(base_url: &str, list_name: &str) -> HashSet<String> {
+	match database_sources::get_text(&database_sources::join_url(base_url, list_name)).await {
+		Ok(text) => text
+			.split('\n')
+			.map(|line| line.trim().to_string())
+			.collect(),
 		Err(err) => {
-			error!("Failed to download ids list {list_name}: {err}");
+			error!("Failed to get ids list {list_name}: {err}");
 			HashSet::default()
 		}
 	}
@@ -47,17 +43,16 @@ async fn get_list(list_name: &str) -> HashSet<String> {
 // TODO this should be a more generic thing where you can load arbitrary json databases,
 // and show them as columns in Rai Pal.
 // For now it's just a hardcoded uevr db.
-async fn get_uevr_scores() -> HashMap<String, UevrScore> {
-	match reqwest::get(format!("{STEAM_APP_IDS_URL_BASE}/uevr-scores.json")).await {
-		Ok(response) => response
-			.json::<HashMap<String, UevrScore>>()
-			.await
-			.unwrap_or_else(|err| {
-				error!("Failed to parse uevr ids list: {err}");
-				HashMap::default()
-			}),
+async fn get_uevr_scores(base_url: &str) -> HashMap<String, UevrScore> {
+	match database_sources::get_text(&database_sources::join_url(base_url, "uevr-scores.json"))
+		.await
+	{
+		Ok(text) => serde_json::from_str(&text).unwrap_or_else(|err| {
+			error!("Failed to parse uevr ids list: {err}");
+			HashMap::default()
+		}),
 		Err(err) => {
-			error!("Failed to download uevr ids list: {err}");
+			error!("Failed to get uevr ids list: {err}");
 			HashMap::default()
 		}
 	}
@@ -74,10 +69,20 @@ fn get_ids_data_list(ids: &HashSet<String>, engine: GameEngineBrand) -> Vec<Stea
 }
 
 pub async fn get() -> Result<HashMap<String, SteamGame>> {
-	let (unity, unreal, godot) =
-		future::join!(get_list("Unity"), get_list("Unreal"), get_list("Godot"),).await;
+	let sources = database_sources::read().unwrap_or_else(|err| {
+		error!("Failed to read database sources, using the official ones: {err}");
+		DatabaseSources::official_only()
+	});
+	let base_url = sources.get_steam_ids_url();
+
+	let (unity, unreal, godot) = future::join!(
+		get_list(base_url, "Unity"),
+		get_list(base_url, "Unreal"),
+		get_list(base_url, "Godot"),
+	)
+	.await;
 
-	let uevr_scores = get_uevr_scores().await;
+	let uevr_scores = get_uevr_scores(base_url).await;
 
 	let mut games = [
 		get_ids_data_list(&unity, GameEngineBrand::Unity),
@@ -1,3 +1,4 @@
 pub mod appinfo;
 pub mod id_lists;
+pub mod proton;
 pub mod thumbnail;
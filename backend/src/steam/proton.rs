@@ -0,0 +1,140 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+
+use crate::{
+	installed_game::InstalledGame,
+	serializable_struct,
+	wine_registry::RegistryFile,
+	Error,
+	Result,
+};
+
+const DLL_OVERRIDES_KEY: &str = "Software\\Wine\\DllOverrides";
+
+// Makes Wine load the dll from the game folder before its own version.
+const NATIVE_OVERRIDE: &str = "native,builtin";
+
+serializable_struct!(WineDllOverrides {
+	pub user_reg_path: PathBuf,
+	// Maps dll name to the raw registry data it had before we touched it, if any.
+	pub previous_values: HashMap<String, Option<String>>,
+});
+
+// Proton keeps a separate Wine prefix for each game, inside the same steamapps folder as the game.
+fn get_user_reg_path(game_path: &Path, steam_app_id: u32) -> Result<PathBuf> {
+	let canonical_game_path = game_path.canonicalize()?;
+
+	let steam_apps_folder = canonical_game_path
+		.ancestors()
+		.find(|ancestor| ancestor.ends_with("steamapps"))
+		.ok_or_else(|| Error::WinePrefixNotFound(game_path.to_path_buf()))?;
+
+	let prefix_folder = steam_apps_folder
+		.join("compatdata")
+		.join(steam_app_id.to_string())
+		.join("pfx");
+
+	let user_reg_path = prefix_folder.join("user.reg");
+
+	if user_reg_path.is_file() {
+		Ok(user_reg_path)
+	} else {
+		Err(Error::WinePrefixNotFound(prefix_folder))
+	}
+}
+
+fn get_overrides_path(game: &InstalledGame) -> Result<PathBuf> {
+	Ok(game
+		.get_installed_mods_folder()?
+		.join("wine-dll-overrides.json"))
+}
+
+fn read_overrides(game: &InstalledGame) -> Result<Option<WineDllOverrides>> {
+	let overrides_path = get_overrides_path(game)?;
+
+	if !overrides_path.is_file() {
+		return Ok(None);
+	}
+
+	Ok(Some(serde_json::from_str(&fs::read_to_string(
+		overrides_path,
+	)?)?))
+}
+
+fn write_registry(user_reg_path: &Path, registry: &RegistryFile) -> Result {
+	// Only the first backup is kept, so it always has the prefix exactly as it was before Rai Pal.
+	let backup_path = user_reg_path.with_extension("reg.rai-pal.bak");
+	if !backup_path.is_file() {
+		fs::copy(user_reg_path, backup_path)?;
+	}
+
+	fs::write(user_reg_path, registry.to_string())?;
+
+	Ok(())
+}
+
+// Tells Wine to use the given dll from the game folder, instead of the builtin one.
+// Whatever value was there before is remembered, so it can be restored on uninstall.
+pub fn add_dll_override(game: &InstalledGame, steam_app_id: u32, dll_name: &str) -> Result {
+	let user_reg_path = get_user_reg_path(&game.executable.path, steam_app_id)?;
+	let mut registry = RegistryFile::parse(&fs::read_to_string(&user_reg_path)?)?;
+
+	let current_value = registry
+		.get_value(DLL_OVERRIDES_KEY, dll_name)
+		.map(ToString::to_string);
+
+	if current_value.as_deref() == Some(&format!("\"{NATIVE_OVERRIDE}\"")) {
+		return Ok(());
+	}
+
+	let mut overrides = read_overrides(game)?.unwrap_or_else(|| WineDllOverrides {
+		user_reg_path: user_reg_path.clone(),
+		previous_values: HashMap::new(),
+	});
+	overrides
+		.previous_values
+		.entry(dll_name.to_string())
+		.or_insert(current_value);
+
+	registry.set_string_value(DLL_OVERRIDES_KEY, dll_name, NATIVE_OVERRIDE);
+	write_registry(&user_reg_path, &registry)?;
+
+	fs::write(
+		get_overrides_path(game)?,
+		serde_json::to_string_pretty(&overrides)?,
+	)?;
+
+	Ok(())
+}
+
+// Puts back every dll override value we changed for this game.
+pub fn remove_dll_overrides(game: &InstalledGame) -> Result {
+	let Some(overrides) = read_overrides(game)? else {
+		return Ok(());
+	};
+
+	// The prefix might have been deleted since, in which case there's nothing to undo.
+	if overrides.user_reg_path.is_file() {
+		let mut registry = RegistryFile::parse(&fs::read_to_string(&overrides.user_reg_path)?)?;
+
+		for (dll_name, previous_value) in &overrides.previous_values {
+			if let Some(previous_value) = previous_value {
+				registry.set_raw_value(DLL_OVERRIDES_KEY, dll_name, previous_value);
+			} else {
+				registry.remove_value(DLL_OVERRIDES_KEY, dll_name);
+			}
+		}
+
+		write_registry(&overrides.user_reg_path, &registry)?;
+	}
+
+	fs::remove_file(get_overrides_path(game)?)?;
+
+	Ok(())
+}
@@ -10,13 +10,19 @@ use winapi::{
 		c_int,
 		c_uint,
 	},
-	um::winuser::{
-		MessageBoxW,
-		IDYES,
-		MB_ICONERROR,
-		MB_OK,
-		MB_SYSTEMMODAL,
-		MB_YESNO,
+	um::{
+		wincon::{
+			AttachConsole,
+			ATTACH_PARENT_PROCESS,
+		},
+		winuser::{
+			MessageBoxW,
+			IDYES,
+			MB_ICONERROR,
+			MB_OK,
+			MB_SYSTEMMODAL,
+			MB_YESNO,
+		},
 	},
 };
 
@@ -47,6 +53,14 @@ pub fn error_dialog(error_text: &str) {
 	base_error_dialog(error_text, MB_OK);
 }
 
+// Release builds don't get a console window, so this lets command line output show up
+// in the terminal the app was started from.
+pub fn attach_parent_console() {
+	unsafe {
+		AttachConsole(ATTACH_PARENT_PROCESS);
+	}
+}
+
 pub fn error_question_dialog(error_text: &str) -> bool {
 	base_error_dialog(error_text, MB_YESNO) == IDYES
 }
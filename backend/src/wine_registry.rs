@@ -0,0 +1,488 @@
+use std::fmt::Write;
+
+use crate::{
+	Error,
+	Result,
+};
+
+// Every Wine registry file starts with this line.
+const REGISTRY_SIGNATURE: &str = "WINE REGISTRY Version 2";
+
+enum RegistryLine {
+	// Raw text includes the `"name"=` part, and any continuation lines for long values.
+	Value { name: String, raw: String },
+	// Metadata like `#time=...`, or anything else we don't need to understand.
+	Other(String),
+}
+
+struct RegistryKey {
+	// Kept escaped, the same way it's written in the file.
+	path: String,
+	// Whatever comes after the key path in the header line, usually a timestamp.
+	header_suffix: String,
+	lines: Vec<RegistryLine>,
+}
+
+// Wine's text registry format (system.reg, user.reg, userdef.reg).
+// Anything we don't touch gets written back exactly as it was read.
+pub struct RegistryFile {
+	header: Vec<String>,
+	keys: Vec<RegistryKey>,
+	// Wine writes `\n`, but files edited on Windows might have `\r\n`, which we keep.
+	line_ending: &'static str,
+}
+
+impl RegistryKey {
+	// Value names are case insensitive too.
+	fn find_value_index(&self, name: &str) -> Option<usize> {
+		self.lines.iter().position(|line| {
+			matches!(line, RegistryLine::Value { name: line_name, .. } if line_name.eq_ignore_ascii_case(name))
+		})
+	}
+}
+
+fn escape(text: &str) -> String {
+	text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unescape(text: &str) -> String {
+	let mut result = String::with_capacity(text.len());
+	let mut chars = text.chars();
+
+	while let Some(character) = chars.next() {
+		if character == '\\' {
+			if let Some(escaped) = chars.next() {
+				result.push(escaped);
+			}
+		} else {
+			result.push(character);
+		}
+	}
+
+	result
+}
+
+// Returns the end index of the quoted string that starts at the beginning of `text`.
+fn find_closing_quote(text: &str) -> Option<usize> {
+	let mut is_escaped = false;
+
+	for (index, character) in text.char_indices().skip(1) {
+		match character {
+			_ if is_escaped => is_escaped = false,
+			'\\' => is_escaped = true,
+			'"' => return Some(index),
+			_ => {}
+		}
+	}
+
+	None
+}
+
+// Key header lines look like `[Software\\Wine\\DllOverrides] 1700000000`.
+fn parse_key_header(line: &str) -> Option<RegistryKey> {
+	let content = line.strip_prefix('[')?;
+	let mut is_escaped = false;
+
+	for (index, character) in content.char_indices() {
+		match character {
+			_ if is_escaped => is_escaped = false,
+			'\\' => is_escaped = true,
+			']' => {
+				return Some(RegistryKey {
+					path: content[..index].to_string(),
+					header_suffix: content[index + 1..].to_string(),
+					lines: Vec::new(),
+				})
+			}
+			_ => {}
+		}
+	}
+
+	None
+}
+
+// Value names can have `=` in them, so the data starts after the name's closing quote.
+fn get_value_data(raw: &str) -> Option<&str> {
+	if let Some(data) = raw.strip_prefix("@=") {
+		return Some(data);
+	}
+
+	raw.get(find_closing_quote(raw)? + 1..)?.strip_prefix('=')
+}
+
+fn parse_value_name(line: &str) -> Result<Option<String>> {
+	if line.starts_with("@=") {
+		return Ok(Some("@".to_string()));
+	}
+
+	if !line.starts_with('"') {
+		return Ok(None);
+	}
+
+	let closing_quote = find_closing_quote(line)
+		.ok_or_else(|| Error::WineRegistryInvalid(format!("Unterminated value name: {line}")))?;
+
+	if line[closing_quote + 1..].starts_with('=') {
+		Ok(Some(unescape(&line[1..closing_quote])))
+	} else {
+		Err(Error::WineRegistryInvalid(format!(
+			"Value name isn't followed by `=`: {line}"
+		)))
+	}
+}
+
+impl RegistryFile {
+	pub fn parse(contents: &str) -> Result<Self> {
+		let mut lines = contents.lines();
+
+		if lines.next().map(str::trim_end) != Some(REGISTRY_SIGNATURE) {
+			return Err(Error::WineRegistryInvalid(format!(
+				"Missing `{REGISTRY_SIGNATURE}` header"
+			)));
+		}
+
+		let mut registry = Self {
+			header: vec![REGISTRY_SIGNATURE.to_string()],
+			keys: Vec::new(),
+			line_ending: if contents.contains("\r\n") {
+				"\r\n"
+			} else {
+				"\n"
+			},
+		};
+
+		// Long values (like hex data) are split over several lines, each ending with a backslash.
+		let mut is_continuation = false;
+
+		for line in lines {
+			if is_continuation {
+				is_continuation = line.ends_with('\\');
+
+				if let Some(RegistryLine::Value { raw, .. }) = registry
+					.keys
+					.last_mut()
+					.and_then(|key| key.lines.last_mut())
+				{
+					raw.push_str(registry.line_ending);
+					raw.push_str(line);
+				}
+				continue;
+			}
+
+			if let Some(key) = parse_key_header(line) {
+				registry.keys.push(key);
+				continue;
+			}
+
+			// Blank lines only matter in the header, since keys always get one before them.
+			let Some(key) = registry.keys.last_mut() else {
+				registry.header.push(line.to_string());
+				continue;
+			};
+
+			if line.trim().is_empty() {
+				continue;
+			}
+
+			if let Some(name) = parse_value_name(line)? {
+				is_continuation = line.ends_with('\\');
+				key.lines.push(RegistryLine::Value {
+					name,
+					raw: line.to_string(),
+				});
+			} else {
+				key.lines.push(RegistryLine::Other(line.to_string()));
+			}
+		}
+
+		Ok(registry)
+	}
+
+	// Key paths in the registry are case insensitive.
+	fn find_key_mut(&mut self, key_path: &str) -> Option<&mut RegistryKey> {
+		let escaped_path = escape(key_path);
+
+		self.keys
+			.iter_mut()
+			.find(|key| key.path.eq_ignore_ascii_case(&escaped_path))
+	}
+
+	fn find_key(&self, key_path: &str) -> Option<&RegistryKey> {
+		let escaped_path = escape(key_path);
+
+		self.keys
+			.iter()
+			.find(|key| key.path.eq_ignore_ascii_case(&escaped_path))
+	}
+
+	// Returns the raw data of the value, like `"native,builtin"` or `dword:00000001`.
+	pub fn get_value(&self, key_path: &str, name: &str) -> Option<&str> {
+		let key = self.find_key(key_path)?;
+
+		match &key.lines[key.find_value_index(name)?] {
+			RegistryLine::Value { raw, .. } => get_value_data(raw),
+			RegistryLine::Other(_) => None,
+		}
+	}
+
+	// Creates the key if it doesn't exist yet.
+	pub fn set_string_value(&mut self, key_path: &str, name: &str, value: &str) {
+		self.set_raw_value(key_path, name, &format!("\"{}\"", escape(value)));
+	}
+
+	// Sets a value using raw data, like the data returned by `get_value` or `remove_value`.
+	pub fn set_raw_value(&mut self, key_path: &str, name: &str, data: &str) {
+		if self.find_key(key_path).is_none() {
+			self.keys.push(RegistryKey {
+				path: escape(key_path),
+				header_suffix: String::new(),
+				lines: Vec::new(),
+			});
+		}
+
+		let Some(key) = self.find_key_mut(key_path) else {
+			return;
+		};
+
+		let new_line = RegistryLine::Value {
+			name: name.to_string(),
+			raw: format!("\"{}\"={data}", escape(name)),
+		};
+
+		if let Some(index) = key.find_value_index(name) {
+			key.lines[index] = new_line;
+		} else {
+			key.lines.push(new_line);
+		}
+	}
+
+	// Returns the raw data the value had before being removed.
+	pub fn remove_value(&mut self, key_path: &str, name: &str) -> Option<String> {
+		let key = self.find_key_mut(key_path)?;
+		let index = key.find_value_index(name)?;
+
+		match key.lines.remove(index) {
+			RegistryLine::Value { raw, .. } => get_value_data(&raw).map(str::to_string),
+			RegistryLine::Other(_) => None,
+		}
+	}
+}
+
+impl std::fmt::Display for RegistryFile {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let header_length = self
+			.header
+			.iter()
+			.rposition(|line| !line.trim().is_empty())
+			.map_or(0, |index| index + 1);
+
+		let line_ending = self.line_ending;
+		let mut output = self.header[..header_length].join(line_ending);
+		output.push_str(line_ending);
+
+		for key in &self.keys {
+			write!(
+				output,
+				"{line_ending}[{}]{}{line_ending}",
+				key.path, key.header_suffix
+			)?;
+
+			for line in &key.lines {
+				match line {
+					RegistryLine::Value { raw, .. } => output.push_str(raw),
+					RegistryLine::Other(text) => output.push_str(text),
+				}
+				output.push_str(line_ending);
+			}
+		}
+
+		formatter.write_str(&output)
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use super::RegistryFile;
+
+	const DLL_OVERRIDES_KEY: &str = "Software\\Wine\\DllOverrides";
+
+	const USER_REG: &str = r#"WINE REGISTRY Version 2
+;; All keys relative to \\User\\S-1-5-21-0-0-0-1000
+
+#arch=win64
+
+[Console] 1700000000
+#time=1da2b3c4d5e6f70
+"ColorTable00"=dword:00000000
+"FaceName"=""
+
+[Software\\Wine\\DllOverrides] 1700000001
+#time=1da2b3c4d5e6f71
+"d3d9"="native"
+"winhttp"="native,builtin"
+
+[Software\\Wine\\Fonts\\Replacements] 1700000002
+#time=1da2b3c4d5e6f72
+"Arial"=hex(7):41,00,72,00,69,00,61,00,6c,00,00,00,41,00,72,00,69,00,61,00,\
+  6c,00,20,00,4e,00,61,00,72,00,72,00,6f,00,77,00,00,00,00,00
+@="default"
+"#;
+
+	fn parse(contents: &str) -> RegistryFile {
+		RegistryFile::parse(contents).unwrap()
+	}
+
+	#[test]
+	fn untouched_file_round_trips() {
+		assert_eq!(parse(USER_REG).to_string(), USER_REG);
+	}
+
+	#[test]
+	fn rejects_missing_signature() {
+		assert!(RegistryFile::parse("[Console] 1700000000\n").is_err());
+	}
+
+	#[test]
+	fn reads_values() {
+		let registry = parse(USER_REG);
+
+		assert_eq!(
+			registry.get_value(DLL_OVERRIDES_KEY, "winhttp"),
+			Some("\"native,builtin\"")
+		);
+		// Key paths and value names are case insensitive.
+		assert_eq!(
+			registry.get_value("software\\wine\\dlloverrides", "D3D9"),
+			Some("\"native\"")
+		);
+		assert_eq!(
+			registry.get_value("Software\\Wine\\Fonts\\Replacements", "@"),
+			Some("\"default\"")
+		);
+	}
+
+	#[test]
+	fn adds_dll_override() {
+		let mut registry = parse(USER_REG);
+		registry.set_string_value(DLL_OVERRIDES_KEY, "version", "native,builtin");
+
+		assert_eq!(
+			registry.to_string(),
+			USER_REG.replace(
+				"\"winhttp\"=\"native,builtin\"\n",
+				"\"winhttp\"=\"native,builtin\"\n\"version\"=\"native,builtin\"\n"
+			)
+		);
+	}
+
+	#[test]
+	fn replaces_dll_override() {
+		let mut registry = parse(USER_REG);
+		registry.set_string_value(DLL_OVERRIDES_KEY, "D3D9", "builtin");
+
+		assert_eq!(
+			registry.to_string(),
+			USER_REG.replace("\"d3d9\"=\"native\"", "\"D3D9\"=\"builtin\"")
+		);
+	}
+
+	#[test]
+	fn removes_dll_override() {
+		let mut registry = parse(USER_REG);
+
+		assert_eq!(
+			registry.remove_value(DLL_OVERRIDES_KEY, "winhttp"),
+			Some("\"native,builtin\"".to_string())
+		);
+		assert_eq!(registry.get_value(DLL_OVERRIDES_KEY, "winhttp"), None);
+		assert_eq!(
+			registry.to_string(),
+			USER_REG.replace("\"winhttp\"=\"native,builtin\"\n", "")
+		);
+
+		// It was the last value in the key, so putting the old data back gets us the same file.
+		registry.set_raw_value(DLL_OVERRIDES_KEY, "winhttp", "\"native,builtin\"");
+		assert_eq!(registry.to_string(), USER_REG);
+	}
+
+	#[test]
+	fn handles_missing_key() {
+		let contents = "WINE REGISTRY Version 2\n;; All keys relative to \\\\User\n\n[Console] 1700000000\n\"FaceName\"=\"\"\n";
+		let mut registry = parse(contents);
+
+		assert_eq!(registry.get_value(DLL_OVERRIDES_KEY, "winhttp"), None);
+		assert_eq!(registry.remove_value(DLL_OVERRIDES_KEY, "winhttp"), None);
+		assert_eq!(registry.to_string(), contents);
+
+		registry.set_string_value(DLL_OVERRIDES_KEY, "winhttp", "native,builtin");
+		assert_eq!(
+			registry.to_string(),
+			format!(
+				"{contents}\n[Software\\\\Wine\\\\DllOverrides]\n\"winhttp\"=\"native,builtin\"\n"
+			)
+		);
+	}
+
+	#[test]
+	fn keeps_continued_hex_values_together() {
+		let mut registry = parse(USER_REG);
+		let fonts_key = "Software\\Wine\\Fonts\\Replacements";
+
+		assert_eq!(
+			registry.get_value(fonts_key, "Arial"),
+			Some("hex(7):41,00,72,00,69,00,61,00,6c,00,00,00,41,00,72,00,69,00,61,00,\\\n  6c,00,20,00,4e,00,61,00,72,00,72,00,6f,00,77,00,00,00,00,00")
+		);
+		// The continuation line isn't mistaken for a value of its own.
+		assert_eq!(registry.get_value(fonts_key, "@"), Some("\"default\""));
+
+		registry.remove_value(fonts_key, "Arial");
+		assert!(!registry.to_string().contains("6c,00,20,00"));
+	}
+
+	#[test]
+	fn escapes_quotes_and_backslashes() {
+		let contents = r#"WINE REGISTRY Version 2
+
+[Software\\Quoted \"Key\"] 1700000000
+"Path \"Name\""="C:\\Games\\\"Quoted\""
+"#;
+		let mut registry = parse(contents);
+		let key_path = "Software\\Quoted \"Key\"";
+
+		assert_eq!(registry.to_string(), contents);
+		assert_eq!(
+			registry.get_value(key_path, "Path \"Name\""),
+			Some(r#""C:\\Games\\\"Quoted\"""#)
+		);
+
+		registry.set_string_value(key_path, "With=Equals", "x=y");
+		assert_eq!(registry.get_value(key_path, "with=equals"), Some("\"x=y\""));
+
+		registry.set_string_value(key_path, "Back\\slash", "a\\b\"c");
+		assert_eq!(
+			registry.get_value(key_path, "Back\\slash"),
+			Some(r#""a\\b\"c""#)
+		);
+		assert!(registry
+			.to_string()
+			.ends_with("\"Back\\\\slash\"=\"a\\\\b\\\"c\"\n"));
+	}
+
+	#[test]
+	fn keeps_crlf_line_endings() {
+		let contents = USER_REG.replace('\n', "\r\n");
+		let mut registry = parse(&contents);
+
+		assert_eq!(registry.to_string(), contents);
+		assert_eq!(
+			registry.get_value(DLL_OVERRIDES_KEY, "winhttp"),
+			Some("\"native,builtin\"")
+		);
+
+		registry.set_string_value(DLL_OVERRIDES_KEY, "version", "native,builtin");
+		assert!(registry
+			.to_string()
+			.contains("\"winhttp\"=\"native,builtin\"\r\n\"version\"=\"native,builtin\"\r\n"));
+	}
+}
@@ -0,0 +1,159 @@
+#!/usr/bin/env python3
+# Generates the tiny .NET assemblies used by the dotnet_assembly tests.
+# They only have the metadata Rai Pal reads (Module, Assembly and AssemblyRef tables), no code.
+# Run from this folder: python3 generate.py
+
+import struct
+
+SECTION_RVA = 0x2000
+FILE_ALIGNMENT = 0x200
+CLI_HEADER_SIZE = 72
+
+
+def pad(data, alignment):
+	return data + b"\0" * (-len(data) % alignment)
+
+
+def build_strings(names):
+	strings = b"\0"
+	indices = {}
+	for name in names:
+		indices[name] = len(strings)
+		strings += name.encode() + b"\0"
+	return pad(strings, 4), indices
+
+
+def build_tables(assembly_name, references, string_indices):
+	module_table = 0x00
+	assembly_table = 0x20
+	assembly_ref_table = 0x23
+	valid = (1 << module_table) | (1 << assembly_table) | (1 << assembly_ref_table)
+
+	tables = struct.pack("<IBBBBQQ", 0, 2, 0, 0, 1, valid, 0)
+	tables += struct.pack("<III", 1, 1, len(references))
+
+	# Module: generation, name, mvid, enc id, enc base id.
+	tables += struct.pack("<HHHHH", 0, string_indices[assembly_name + ".dll"], 1, 0, 0)
+
+	# Assembly: hash algorithm, version, flags, public key, name, culture.
+	tables += struct.pack("<IHHHHIHHH", 0x8004, 1, 0, 0, 0, 0, 0, string_indices[assembly_name], 0)
+
+	# AssemblyRef: version, flags, public key or token, name, culture, hash value.
+	for reference in references:
+		tables += struct.pack("<HHHHIHHHH", 4, 0, 0, 0, 0, 0, string_indices[reference], 0, 0)
+
+	return pad(tables, 4)
+
+
+def build_metadata(assembly_name, references):
+	strings, string_indices = build_strings([assembly_name + ".dll", assembly_name] + references)
+	streams = [
+		(b"#~", build_tables(assembly_name, references, string_indices)),
+		(b"#Strings", strings),
+		(b"#GUID", bytes(range(16))),
+		(b"#Blob", b"\0" * 4),
+	]
+
+	version = pad(b"v4.0.30319\0", 4)
+	root = struct.pack("<IHHII", 0x424A5342, 1, 1, 0, len(version)) + version
+	root += struct.pack("<HH", 0, len(streams))
+
+	headers_size = sum(8 + len(pad(name + b"\0", 4)) for name, _ in streams)
+	offset = len(root) + headers_size
+	stream_headers = b""
+	stream_data = b""
+	for name, data in streams:
+		stream_headers += struct.pack("<II", offset, len(data)) + pad(name + b"\0", 4)
+		stream_data += data
+		offset += len(data)
+
+	return root + stream_headers + stream_data
+
+
+def build_pe(section_data, has_cli_header):
+	section_raw_size = len(pad(section_data, FILE_ALIGNMENT))
+
+	dos_header = b"MZ" + b"\0" * 58 + struct.pack("<I", 0x80)
+	dos_header = dos_header.ljust(0x80, b"\0")
+
+	coff_header = b"PE\0\0" + struct.pack("<HHIIIHH", 0x14C, 1, 0, 0, 0, 0xE0, 0x2102)
+
+	optional_header = struct.pack(
+		"<HBBIIIIIIIIIHHHHHHIIIIHHIIIIII",
+		0x10B,  # PE32
+		8,
+		0,
+		section_raw_size,
+		0,
+		0,
+		0,  # No entry point
+		SECTION_RVA,
+		0,
+		0x10000000,
+		0x2000,  # Section alignment
+		FILE_ALIGNMENT,
+		4,
+		0,
+		0,
+		0,
+		4,
+		0,
+		0,
+		SECTION_RVA + 0x2000,  # Size of image
+		FILE_ALIGNMENT,  # Size of headers
+		0,
+		3,
+		0x8540,
+		0x100000,
+		0x1000,
+		0x100000,
+		0x1000,
+		0,
+		16,
+	)
+	data_directories = [(0, 0)] * 16
+	if has_cli_header:
+		data_directories[14] = (SECTION_RVA, CLI_HEADER_SIZE)
+	for rva, size in data_directories:
+		optional_header += struct.pack("<II", rva, size)
+
+	section_header = struct.pack(
+		"<8sIIIIIIHHI",
+		b".text",
+		len(section_data),
+		SECTION_RVA,
+		section_raw_size,
+		FILE_ALIGNMENT,
+		0,
+		0,
+		0,
+		0,
+		0x60000020,
+	)
+
+	headers = pad(dos_header + coff_header + optional_header + section_header, FILE_ALIGNMENT)
+	return headers + pad(section_data, FILE_ALIGNMENT)
+
+
+def build_assembly(assembly_name, references):
+	metadata = build_metadata(assembly_name, references)
+	metadata_rva = SECTION_RVA + CLI_HEADER_SIZE
+	cli_header = struct.pack("<IHHIIII", CLI_HEADER_SIZE, 2, 5, metadata_rva, len(metadata), 1, 0)
+	cli_header = cli_header.ljust(CLI_HEADER_SIZE, b"\0")
+
+	return build_pe(cli_header + metadata, True)
+
+
+ASSEMBLIES = {
+	"mono.dll": build_assembly("MonoMod", ["mscorlib", "UnityEngine", "BepInEx"]),
+	"il2cpp.dll": build_assembly(
+		"Il2CppMod",
+		["mscorlib", "UnityEngine.CoreModule", "Il2CppInterop.Runtime", "BepInEx.Unity.IL2CPP"],
+	),
+	"plain.dll": build_assembly("PlainLibrary", ["mscorlib", "System", "System.Core"]),
+	"native.dll": build_pe(b"\0" * 16, False),
+}
+
+for file_name, contents in ASSEMBLIES.items():
+	with open(file_name, "wb") as file:
+		file.write(contents)
@@ -11,7 +11,7 @@ declare global {
 const invoke = () => window.__TAURI_INVOKE__;
 
 export function dummyCommand() {
-    return invoke()<[InstalledGame, AppEvent]>("dummy_command")
+    return invoke()<[InstalledGame, AppEvent, ModProgress, RunnableExit]>("dummy_command")
 }
 
 export function updateData() {
@@ -34,14 +34,78 @@ export function openGameFolder(gameId: string) {
     return invoke()<null>("open_game_folder", { gameId })
 }
 
-export function installMod(gameId: string, modId: string) {
-    return invoke()<null>("install_mod", { gameId,modId })
+export function getInstallPlan(gameId: string, modId: string) {
+    return invoke()<InstallPlan>("get_install_plan", { gameId,modId })
+}
+
+export function installMod(gameId: string, modId: string, approvedModIds: string[]) {
+    return invoke()<null>("install_mod", { gameId,modId,approvedModIds })
+}
+
+export function installModVersion(gameId: string, modId: string, versionId: string) {
+    return invoke()<null>("install_mod_version", { gameId,modId,versionId })
+}
+
+export function updateMod(gameId: string, modId: string) {
+    return invoke()<null>("update_mod", { gameId,modId })
+}
+
+export function getModUpdates() {
+    return invoke()<{ [key: string]: ModUpdate[] }>("get_mod_updates")
 }
 
 export function uninstallMod(gameId: string, modId: string) {
     return invoke()<null>("uninstall_mod", { gameId,modId })
 }
 
+export function uninstallModLoader(gameId: string, modLoaderId: string) {
+    return invoke()<null>("uninstall_mod_loader", { gameId,modLoaderId })
+}
+
+export function setModEnabled(gameId: string, modId: string, enabled: boolean) {
+    return invoke()<null>("set_mod_enabled", { gameId,modId,enabled })
+}
+
+export function setModLoaderEnabled(gameId: string, modLoaderId: string, enabled: boolean) {
+    return invoke()<null>("set_mod_loader_enabled", { gameId,modLoaderId,enabled })
+}
+
+export function getBepinexConfigFiles(gameId: string) {
+    return invoke()<string[]>("get_bepinex_config_files", { gameId })
+}
+
+export function getBepinexConfig(gameId: string, fileName: string) {
+    return invoke()<BepInExConfig>("get_bepinex_config", { gameId,fileName })
+}
+
+export function setBepinexConfigValue(gameId: string, fileName: string, section: string, key: string, value: string) {
+    return invoke()<null>("set_bepinex_config_value", { gameId,fileName,section,key,value })
+}
+
+export function getBepinexLaunchOptions(gameId: string) {
+    return invoke()<string>("get_bepinex_launch_options", { gameId })
+}
+
+export function getModProfiles(gameId: string) {
+    return invoke()<ModProfiles>("get_mod_profiles", { gameId })
+}
+
+export function saveModProfile(gameId: string, profileName: string) {
+    return invoke()<null>("save_mod_profile", { gameId,profileName })
+}
+
+export function deleteModProfile(gameId: string, profileName: string) {
+    return invoke()<null>("delete_mod_profile", { gameId,profileName })
+}
+
+export function switchModProfile(gameId: string, profileName: string) {
+    return invoke()<null>("switch_mod_profile", { gameId,profileName })
+}
+
+export function restoreGameToVanilla(gameId: string) {
+    return invoke()<null>("restore_game_to_vanilla", { gameId })
+}
+
 export function openGameModsFolder(gameId: string) {
     return invoke()<null>("open_game_mods_folder", { gameId })
 }
@@ -66,6 +130,10 @@ export function openModsFolder() {
     return invoke()<null>("open_mods_folder")
 }
 
+export function importMod(path: string) {
+    return invoke()<string>("import_mod", { path })
+}
+
 export function addGame(path: string) {
     return invoke()<null>("add_game", { path })
 }
@@ -98,6 +166,26 @@ export function refreshGame(gameId: string) {
     return invoke()<null>("refresh_game", { gameId })
 }
 
+export function startGameAndRunMod(gameId: string, modId: string) {
+    return invoke()<null>("start_game_and_run_mod", { gameId,modId })
+}
+
+export function getRunningRunnables() {
+    return invoke()<RunningRunnable[]>("get_running_runnables")
+}
+
+export function stopRunnable(pid: number) {
+    return invoke()<null>("stop_runnable", { pid })
+}
+
+export function getDatabaseSources() {
+    return invoke()<DatabaseSources>("get_database_sources")
+}
+
+export function setDatabaseSources(sources: DatabaseSources) {
+    return invoke()<null>("set_database_sources", { sources })
+}
+
 export function openLogsFolder() {
     return invoke()<null>("open_logs_folder")
 }
@@ -115,22 +203,34 @@ export function openGamePage(ownedGameId: string) {
 }
 
 export type GameEngineVersion = { major: number; minor: number; patch: number; suffix: string | null; display: string }
-export type Manifest = { version: string; runnable: RunnableModData | null; engine: GameEngineBrand | null; unityBackend: UnityScriptingBackend | null }
+export type Manifest = { version: string; runnable: RunnableModData | null; engine: GameEngineBrand | null; unityBackend: UnityScriptingBackend | null; dependencies: string[]; conflicts: string[]; pinned: boolean; disabled: boolean; loaderId: string | null }
 export type ProviderId = "Steam" | "Manual" | "Epic" | "Gog" | "Xbox"
 export type GameEngineBrand = "Unity" | "Unreal" | "Godot"
 export type GameMode = "VR" | "Flat"
 export type ModKind = "Installable" | "Runnable"
-export type RunnableModData = { path: string; args: string[] }
-export type AppEvent = "SyncInstalledGames" | "SyncOwnedGames" | "SyncModLoaders" | "SyncLocalMods" | "SyncRemoteMods" | "ExecutedProviderCommand" | "GameAdded" | "GameRemoved" | "Error"
-export type ModDownload = { id: string; url: string; root: string | null; runnable: RunnableModData | null }
+export type RunnableModData = { path: string; args: string[]; conditionalArgs: RunnableConditionalArgs[] }
+export type RunnableArgsCondition = { engine: GameEngineBrand | null; engineMajorVersion: number | null; operatingSystem: OperatingSystem | null; architecture: Architecture | null }
+export type RunnableConditionalArgs = { condition: RunnableArgsCondition; args: string[] }
+export type AppEvent = "SyncInstalledGames" | "SyncOwnedGames" | "SyncModLoaders" | "SyncLocalMods" | "SyncRemoteMods" | "SyncRunningRunnables" | "ExecutedProviderCommand" | "GameAdded" | "GameRemoved" | "ModProgress" | "RunnableExited" | "Error"
+export type RunningRunnable = { pid: number; modId: string; gameId: string; startTime: BigInt; logPath: string }
+export type RunnableExit = { runnable: RunningRunnable; exitCode: number | null }
+export type ModDownload = { id: string; url: string; sha256: string | null; signature: string | null; root: string | null; runnable: RunnableModData | null }
 export type OwnedGame = { id: string; provider: ProviderId; name: string; osList: OperatingSystem[]; engine: GameEngine | null; releaseDate: BigInt | null; thumbnailUrl: string | null; gameMode: GameMode | null; uevrScore: UevrScore | null; showLibraryCommand: ProviderCommand | null; openPageCommand: ProviderCommand | null; installCommand: ProviderCommand | null }
 export type LocalMod = { data: LocalModData; common: CommonModData }
-export type RemoteModData = { title: string; author: string; sourceCode: string; description: string; latestVersion: ModDownload | null }
+export type RemoteModData = { title: string; author: string; sourceCode: string; description: string; latestVersion: ModDownload | null; versions: ModDownload[]; dependencies: string[]; conflicts: string[]; sourceId: string; signingKeys: string[]; databaseCachedAt: bigint | null }
 export type ProviderCommand = { String: string } | { Path: [string, string[]] }
-export type LocalModData = { path: string; manifest: Manifest | null }
+export type LocalModData = { path: string; manifest: Manifest | null; detectedUnityBackend: UnityScriptingBackend | null }
+export type DatabaseSources = { modDatabases: ModDatabaseSource[]; excludeDefaultModDatabase: boolean; steamIdsUrl: string | null; requireSignatures: boolean }
+export type ModDatabaseSource = { id: string; url: string; loaderIds: string[]; publicKeys: string[] }
 export type ModLoaderData = { id: string; path: string; kind: ModKind }
 export type UnityScriptingBackend = "Il2Cpp" | "Mono"
-export type InstalledGame = { id: string; name: string; provider: ProviderId; executable: GameExecutable; installedModVersions: { [key: string]: string | null }; discriminator: string | null; thumbnailUrl: string | null; ownedGameId: string | null; startCommand: ProviderCommand | null }
+export type BepInExConfigRange = { min: string; max: string }
+export type BepInExConfigEntry = { key: string; value: string; description: string | null; settingType: string | null; defaultValue: string | null; acceptableValues: string[] | null; acceptableRange: BepInExConfigRange | null; comments: string[] }
+export type BepInExConfigSection = { name: string; entries: BepInExConfigEntry[] }
+export type BepInExConfig = { header: string[]; sections: BepInExConfigSection[] }
+export type ModProfile = { name: string; modVersions: { [key: string]: string }; pinnedMods: string[]; disabledMods: string[]; disabledModLoaders: string[]; loaderConfigs: { [key: string]: { [key: string]: string } } }
+export type ModProfiles = { active: string | null; profiles: { [key: string]: ModProfile } }
+export type InstalledGame = { id: string; name: string; provider: ProviderId; executable: GameExecutable; installedModVersions: { [key: string]: string | null }; pinnedMods: string[]; disabledMods: string[]; disabledModLoaders: string[]; modProfileNames: string[]; activeModProfile: string | null; discriminator: string | null; thumbnailUrl: string | null; ownedGameId: string | null; providerGameId: string | null; startCommand: ProviderCommand | null; steamAppId: number | null }
 export type GameExecutable = { path: string; name: string; engine: GameEngine | null; architecture: Architecture | null; operatingSystem: OperatingSystem | null; scriptingBackend: UnityScriptingBackend | null }
 export type RemoteMod = { common: CommonModData; data: RemoteModData }
 export type UevrScore = "A" | "B" | "C" | "D" | "E"
@@ -138,3 +238,10 @@ export type GameEngine = { brand: GameEngineBrand; version: GameEngineVersion |
 export type OperatingSystem = "Linux" | "Windows"
 export type CommonModData = { id: string; engine: GameEngineBrand | null; unityBackend: UnityScriptingBackend | null; loaderId: string }
 export type Architecture = "X64" | "X86"
+export type ModUpdate = { modId: string; installedVersion: string; latestVersion: string }
+export type InstallPlan = { steps: InstallStep[]; conflicts: ModConflict[] }
+export type InstallStep = { modId: string; loaderId: string; requiredBy: string | null; needsDownload: boolean; isInstalled: boolean }
+export type ModConflict = { modId: string; conflictingModId: string }
+export type ModProgress = { modId: string; phase: ProgressPhase; current: BigInt; total: BigInt | null; entries: ArchiveEntry[] }
+export type ArchiveEntry = { path: string; isDir: boolean; size: BigInt }
+export type ProgressPhase = "Download" | "Extract" | "Copy" | "WaitForGame" | "Finished"
@@ -1,3 +1,4 @@
+import { RunnableExit } from "@api/bindings";
 import { useAppEvent } from "@hooks/use-app-event";
 import { DefaultMantineColor } from "@mantine/core";
 import { Notifications, notifications } from "@mantine/notifications";
@@ -55,5 +56,16 @@ export function AppNotifications() {
 		showAppNotification(`Removed game "${gameName}"`, "success");
 	});
 
+	useAppEvent<RunnableExit>("RunnableExited", ({ runnable, exitCode }) => {
+		if (exitCode === 0) {
+			showAppNotification(`${runnable.modId} finished running`, "info");
+		} else {
+			showAppNotification(
+				`${runnable.modId} stopped with exit code ${exitCode ?? "unknown"}. Check the log at ${runnable.logPath}`,
+				"error",
+			);
+		}
+	});
+
 	return <Notifications />;
 }
@@ -1,10 +1,24 @@
-import { DefaultMantineColor, Table, ThemeIcon, Group } from "@mantine/core";
 import {
+	DefaultMantineColor,
+	Table,
+	ThemeIcon,
+	Group,
+	Progress,
+	Text,
+	Spoiler,
+	List,
+} from "@mantine/core";
+import {
+	InstallPlan,
 	ModLoaderData,
 	downloadMod,
+	getInstallPlan,
 	installMod,
 	openModFolder,
+	setModEnabled,
+	startGameAndRunMod,
 	uninstallMod,
+	updateMod,
 } from "@api/bindings";
 import { CommandButton } from "@components/command-button";
 import {
@@ -12,17 +26,24 @@ import {
 	IconCirclePlus,
 	IconFolderOpen,
 	IconMinus,
+	IconPlayerPause,
 	IconPlayerPlay,
 	IconRefreshAlert,
+	IconRocket,
 	IconTrash,
 } from "@tabler/icons-react";
 import { UnifiedMod } from "@hooks/use-unified-mods";
 import { getIsOutdated } from "../../util/is-outdated";
 import { OutdatedMarker } from "@components/OutdatedMarker";
 import { ProcessedInstalledGame } from "@hooks/use-processed-installed-games";
-import { useCallback } from "react";
+import { useCallback, useState } from "react";
 import { ItemName } from "@components/item-name";
 import { MutedText } from "@components/muted-text";
+import { getProgressPercent, useModProgress } from "@hooks/use-mod-progress";
+import {
+	InstallPlanModal,
+	getExtraInstallSteps,
+} from "./install-plan-modal";
 
 type Props = {
 	readonly game: ProcessedInstalledGame;
@@ -32,17 +53,27 @@ type Props = {
 
 export function GameModRow(props: Props) {
 	const installedVersion = props.game.installedModVersions[props.mod.common.id];
-	const isInstalledModOutdated = getIsOutdated(
-		installedVersion,
-		props.mod.remote?.latestVersion?.id,
-	);
+	const isPinned = props.game.pinnedMods.includes(props.mod.common.id);
+	const isInstalledModOutdated =
+		!isPinned &&
+		getIsOutdated(installedVersion, props.mod.remote?.latestVersion?.id);
 	const isLocalModOutdated = getIsOutdated(
 		props.mod.local?.manifest?.version,
 		props.mod.remote?.latestVersion?.id,
 	);
 	const isInstalled = Boolean(installedVersion);
+	const isDisabled = props.game.disabledMods.includes(props.mod.common.id);
+	const detectedUnityBackend = props.mod.local?.detectedUnityBackend;
+	const isUnityBackendMismatch =
+		Boolean(detectedUnityBackend) &&
+		Boolean(props.game.executable.scriptingBackend) &&
+		detectedUnityBackend !== props.game.executable.scriptingBackend;
+	const [progress, clearProgress, progressEntries] = useModProgress(
+		props.mod.common.id,
+	);
+	const [pendingInstallPlan, setPendingInstallPlan] = useState<InstallPlan>();
 
-	const handleClick = useCallback(async () => {
+	const runAction = useCallback(async () => {
 		if (
 			props.modLoader.kind === "Runnable" &&
 			!props.mod.local &&
@@ -52,6 +83,11 @@ export function GameModRow(props: Props) {
 			return;
 		}
 
+		if (isInstalled && isInstalledModOutdated) {
+			await updateMod(props.game.id, props.mod.common.id);
+			return;
+		}
+
 		if (isLocalModOutdated) {
 			await downloadMod(props.mod.common.id);
 		} else if (isInstalled && !isInstalledModOutdated) {
@@ -59,7 +95,14 @@ export function GameModRow(props: Props) {
 			return;
 		}
 
-		await installMod(props.game.id, props.mod.common.id);
+		// Anything beyond the requested mod needs to be confirmed first.
+		const plan = await getInstallPlan(props.game.id, props.mod.common.id);
+		if (getExtraInstallSteps(plan).length > 0 || plan.conflicts.length > 0) {
+			setPendingInstallPlan(plan);
+			return;
+		}
+
+		await installMod(props.game.id, props.mod.common.id, []);
 	}, [
 		props.modLoader.kind,
 		props.mod.local,
@@ -71,6 +114,21 @@ export function GameModRow(props: Props) {
 		isInstalledModOutdated,
 	]);
 
+	const confirmInstallPlan = useCallback(
+		(plan: InstallPlan) =>
+			installMod(
+				props.game.id,
+				props.mod.common.id,
+				getExtraInstallSteps(plan).map((step) => step.modId),
+			).finally(clearProgress),
+		[props.game.id, props.mod.common.id, clearProgress],
+	);
+
+	const handleClick = useCallback(
+		() => runAction().finally(clearProgress),
+		[runAction, clearProgress],
+	);
+
 	const versionText =
 		!isInstalled || isInstalledModOutdated
 			? props.mod.remote?.latestVersion?.id
@@ -134,9 +192,71 @@ export function GameModRow(props: Props) {
 				{props.mod.remote?.description && (
 					<MutedText>{props.mod.remote.description}</MutedText>
 				)}
+				{props.mod.remote && props.mod.remote.dependencies.length > 0 && (
+					<MutedText>
+						Requires: {props.mod.remote.dependencies.join(", ")}
+					</MutedText>
+				)}
+				{isUnityBackendMismatch && (
+					<Text
+						size="xs"
+						c="red"
+					>
+						This mod was made for {detectedUnityBackend} games, but this game
+						uses {props.game.executable.scriptingBackend}.
+					</Text>
+				)}
+				{progress && (
+					<Progress
+						size="xs"
+						animated
+						value={getProgressPercent(progress)}
+					/>
+				)}
+				{progressEntries.length > 0 && (
+					<Spoiler
+						maxHeight={0}
+						showLabel={`Show ${progressEntries.length} files being written`}
+						hideLabel="Hide files"
+						fz="xs"
+					>
+						<List size="xs">
+							{progressEntries.map((entry) => (
+								<List.Item key={entry.path}>{entry.path}</List.Item>
+							))}
+						</List>
+					</Spoiler>
+				)}
 			</Table.Td>
 			<Table.Td>
-				<Group>
+				<Group wrap="nowrap">
+					{isInstalled && props.modLoader.kind === "Installable" && (
+						<CommandButton
+							size="xs"
+							variant="default"
+							leftSection={isDisabled ? <IconPlayerPlay /> : <IconPlayerPause />}
+							onClick={() =>
+								setModEnabled(props.game.id, props.mod.common.id, isDisabled)
+							}
+						>
+							{isDisabled ? "Enable" : "Disable"}
+						</CommandButton>
+					)}
+					{props.modLoader.kind === "Runnable" &&
+						(props.mod.local || props.mod.remote) && (
+							<CommandButton
+								size="xs"
+								variant="default"
+								leftSection={<IconRocket />}
+								onClick={() =>
+									startGameAndRunMod(props.game.id, props.mod.common.id).finally(
+										clearProgress,
+									)
+								}
+							>
+								Start game and run
+							</CommandButton>
+						)}
 					<CommandButton
 						fullWidth
 						color={buttonColor}
@@ -154,6 +274,14 @@ export function GameModRow(props: Props) {
 						{actionText} {versionText}
 					</CommandButton>
 				</Group>
+				{pendingInstallPlan && (
+					<InstallPlanModal
+						plan={pendingInstallPlan}
+						modId={props.mod.common.id}
+						onConfirm={() => confirmInstallPlan(pendingInstallPlan)}
+						onClose={() => setPendingInstallPlan(undefined)}
+					/>
+				)}
 			</Table.Td>
 		</Table.Tr>
 	);
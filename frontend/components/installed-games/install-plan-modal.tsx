@@ -0,0 +1,78 @@
+import { Button, Group, List, Modal, Stack, Text } from "@mantine/core";
+import { InstallPlan, InstallStep } from "@api/bindings";
+import { CommandButton } from "@components/command-button";
+import { IconArrowBack, IconCirclePlus } from "@tabler/icons-react";
+
+type Props = {
+	readonly plan: InstallPlan;
+	readonly modId: string;
+	readonly onConfirm: () => Promise<unknown>;
+	readonly onClose: () => void;
+};
+
+// Dependencies that aren't installed yet, so they'd get installed along with the requested mod.
+export function getExtraInstallSteps(plan: InstallPlan): InstallStep[] {
+	return plan.steps.filter((step) => step.requiredBy && !step.isInstalled);
+}
+
+export function InstallPlanModal(props: Props) {
+	const extraSteps = getExtraInstallSteps(props.plan);
+	const hasConflicts = props.plan.conflicts.length > 0;
+
+	return (
+		<Modal
+			opened
+			centered
+			onClose={props.onClose}
+			title={`Install ${props.modId}`}
+		>
+			<Stack>
+				{extraSteps.length > 0 && (
+					<>
+						<Text>These mods will be installed too:</Text>
+						<List>
+							{extraSteps.map((step) => (
+								<List.Item key={step.modId}>
+									{step.modId} (required by {step.requiredBy}
+									{step.needsDownload && ", will be downloaded"})
+								</List.Item>
+							))}
+						</List>
+					</>
+				)}
+				{hasConflicts && (
+					<>
+						<Text c="red">
+							Can&apos;t install, because some mods don&apos;t work together:
+						</Text>
+						<List c="red">
+							{props.plan.conflicts.map((conflict) => (
+								<List.Item
+									key={`${conflict.modId}-${conflict.conflictingModId}`}
+								>
+									{conflict.modId} conflicts with {conflict.conflictingModId}
+								</List.Item>
+							))}
+						</List>
+					</>
+				)}
+				<Group justify="end">
+					<Button
+						leftSection={<IconArrowBack />}
+						onClick={props.onClose}
+					>
+						Cancel
+					</Button>
+					<CommandButton
+						leftSection={<IconCirclePlus />}
+						disabled={hasConflicts}
+						onClick={props.onConfirm}
+						onSuccess={props.onClose}
+					>
+						Install
+					</CommandButton>
+				</Group>
+			</Stack>
+		</Modal>
+	);
+}
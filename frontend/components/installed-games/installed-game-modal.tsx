@@ -10,16 +10,21 @@ import {
 } from "@mantine/core";
 import {
 	ProviderId,
+	getBepinexLaunchOptions,
 	openGameFolder,
 	openGameModsFolder,
 	openGamePage,
 	refreshGame,
 	removeGame,
+	restoreGameToVanilla,
+	setModLoaderEnabled,
+	uninstallModLoader,
 	showGameInLibrary,
 	startGame,
 	startGameExe,
+	stopRunnable,
 } from "@api/bindings";
-import { useMemo } from "react";
+import { Fragment, useMemo } from "react";
 import { ItemName } from "../item-name";
 import { CommandButton } from "@components/command-button";
 import {
@@ -29,25 +34,31 @@ import {
 	IconBrandSteam,
 	IconBrandXbox,
 	IconBrowser,
+	IconCopy,
 	IconCircleLetterG,
 	IconDeviceGamepad,
 	IconFolder,
 	IconFolderCog,
 	IconFolderOpen,
+	IconPlayerPause,
 	IconPlayerPlay,
+	IconPlayerStop,
+	IconRestore,
 	IconRefresh,
 	IconSquareLetterE,
 	IconTrash,
+	IconTrashX,
 } from "@tabler/icons-react";
 import { ModalImage } from "@components/modal-image";
 import { useAtomValue } from "jotai";
-import { modLoadersAtom } from "@hooks/use-data";
+import { modLoadersAtom, runningRunnablesAtom } from "@hooks/use-data";
 import { DebugData } from "@components/debug-data";
 import { useUnifiedMods } from "@hooks/use-unified-mods";
 import { installedGamesColumns } from "./installed-games-columns";
 import { TableItemDetails } from "@components/table/table-item-details";
 import { ProcessedInstalledGame } from "@hooks/use-processed-installed-games";
 import { GameModRow } from "./game-mod-row";
+import { ModProfilesDropdown } from "./mod-profiles-dropdown";
 import { TableContainer } from "@components/table/table-container";
 import { CommandDropdown } from "@components/command-dropdown";
 import { getThumbnailWithFallback } from "../../util/fallback-thumbnail";
@@ -71,6 +82,9 @@ function getProviderIcon(providerId: ProviderId) {
 
 export function InstalledGameModal(props: Props) {
 	const modLoaderMap = useAtomValue(modLoadersAtom);
+	const runningRunnables = useAtomValue(runningRunnablesAtom).filter(
+		(runnable) => runnable.gameId === props.game.id,
+	);
 	const mods = useUnifiedMods();
 
 	const filteredMods = useMemo(() => {
@@ -79,6 +93,14 @@ export function InstalledGameModal(props: Props) {
 		);
 	}, [mods, props.game.installedModVersions]);
 
+	const installableModLoaders = useMemo(
+		() =>
+			Object.values(modLoaderMap).filter(
+				(modLoader) => modLoader.kind === "Installable",
+			),
+		[modLoaderMap],
+	);
+
 	const ProviderIcon = getProviderIcon(props.game.provider);
 	const ownedGame = props.game.ownedGame;
 
@@ -177,6 +199,66 @@ export function InstalledGameModal(props: Props) {
 							)}
 						</CommandDropdown>
 					)}
+					<ModProfilesDropdown game={props.game} />
+					{installableModLoaders.length > 0 && (
+						<CommandDropdown
+							label="Mod Loaders"
+							icon={<IconTrashX />}
+						>
+							{installableModLoaders.map((modLoader) => {
+								const isDisabled = props.game.disabledModLoaders.includes(
+									modLoader.id,
+								);
+								return (
+									<Fragment key={modLoader.id}>
+										<CommandButton
+											leftSection={
+												isDisabled ? <IconPlayerPlay /> : <IconPlayerPause />
+											}
+											onClick={() =>
+												setModLoaderEnabled(
+													props.game.id,
+													modLoader.id,
+													isDisabled,
+												)
+											}
+										>
+											{isDisabled ? "Enable" : "Disable"} {modLoader.id}
+										</CommandButton>
+										{modLoader.id === "bepinex" &&
+											props.game.executable.operatingSystem === "Linux" && (
+												<CommandButton
+													leftSection={<IconCopy />}
+													onClick={async () =>
+														navigator.clipboard.writeText(
+															await getBepinexLaunchOptions(props.game.id),
+														)
+													}
+												>
+													Copy Steam launch options for {modLoader.id}
+												</CommandButton>
+											)}
+										<CommandButton
+											leftSection={<IconTrashX />}
+											confirmationText={`This will remove ${modLoader.id} and all of its mods from this game. Are you sure?`}
+											onClick={() =>
+												uninstallModLoader(props.game.id, modLoader.id)
+											}
+										>
+											Uninstall {modLoader.id}
+										</CommandButton>
+									</Fragment>
+								);
+							})}
+						</CommandDropdown>
+					)}
+					<CommandButton
+						onClick={() => restoreGameToVanilla(props.game.id)}
+						confirmationText="This will uninstall all mods from this game, and undo every change Rai Pal made to the game files. Are you sure?"
+						leftSection={<IconRestore />}
+					>
+						Restore to Vanilla
+					</CommandButton>
 					{props.game.provider === "Manual" && (
 						<CommandButton
 							onClick={() => removeGame(props.game.id)}
@@ -188,6 +270,19 @@ export function InstalledGameModal(props: Props) {
 						</CommandButton>
 					)}
 				</Group>
+				{runningRunnables.map((runnable) => (
+					<Alert key={runnable.pid}>
+						<Group justify="space-between">
+							{runnable.modId} is running (process {runnable.pid})
+							<CommandButton
+								leftSection={<IconPlayerStop />}
+								onClick={() => stopRunnable(runnable.pid)}
+							>
+								Stop
+							</CommandButton>
+						</Group>
+					</Alert>
+				))}
 				{(!props.game.executable.architecture ||
 					!props.game.executable.operatingSystem) && (
 					<Alert color="red">
@@ -0,0 +1,78 @@
+import { Group, TextInput } from "@mantine/core";
+import {
+	InstalledGame,
+	deleteModProfile,
+	saveModProfile,
+	switchModProfile,
+} from "@api/bindings";
+import { useState } from "react";
+import { CommandButton } from "@components/command-button";
+import { CommandDropdown } from "@components/command-dropdown";
+import {
+	IconCheck,
+	IconDeviceFloppy,
+	IconStack2,
+	IconTrash,
+} from "@tabler/icons-react";
+
+type Props = {
+	readonly game: InstalledGame;
+};
+
+export function ModProfilesDropdown(props: Props) {
+	const [newProfileName, setNewProfileName] = useState("");
+	const active = props.game.activeModProfile;
+
+	return (
+		<CommandDropdown
+			label={active ? `Profile: ${active}` : "Profiles"}
+			icon={<IconStack2 />}
+		>
+			{props.game.modProfileNames.map((profileName) => (
+				<Group
+					key={profileName}
+					gap="xs"
+					wrap="nowrap"
+				>
+					<CommandButton
+						fullWidth
+						leftSection={profileName === active ? <IconCheck /> : undefined}
+						onClick={() => switchModProfile(props.game.id, profileName)}
+					>
+						{profileName}
+					</CommandButton>
+					<CommandButton
+						confirmationText={`Replace profile "${profileName}" with the mods currently installed?`}
+						onClick={() => saveModProfile(props.game.id, profileName)}
+					>
+						<IconDeviceFloppy />
+					</CommandButton>
+					<CommandButton
+						confirmationText={`Delete profile "${profileName}"? Installed mods won't be changed.`}
+						onClick={() => deleteModProfile(props.game.id, profileName)}
+					>
+						<IconTrash />
+					</CommandButton>
+				</Group>
+			))}
+			<Group
+				gap="xs"
+				wrap="nowrap"
+			>
+				<TextInput
+					placeholder="New profile name"
+					value={newProfileName}
+					onChange={(event) => setNewProfileName(event.currentTarget.value)}
+				/>
+				<CommandButton
+					disabled={newProfileName.trim().length === 0}
+					leftSection={<IconDeviceFloppy />}
+					onClick={() => saveModProfile(props.game.id, newProfileName.trim())}
+					onSuccess={() => setNewProfileName("")}
+				>
+					Save current mods
+				</CommandButton>
+			</Group>
+		</CommandDropdown>
+	);
+}
@@ -0,0 +1,46 @@
+import { importMod } from "@api/bindings";
+import { CommandButton } from "@components/command-button";
+import { CommandDropdown } from "@components/command-dropdown";
+import { IconFileZip, IconFolder, IconPackageImport } from "@tabler/icons-react";
+import { dialog } from "@tauri-apps/api";
+
+async function importFromDialog(directory: boolean) {
+	const result = await dialog.open({
+		multiple: false,
+		directory,
+		title: directory ? "Select the mod folder" : "Select the mod zip file",
+		filters: directory
+			? undefined
+			: [
+					{
+						extensions: ["zip"],
+						name: "Zip archive",
+					},
+				],
+	});
+	if (!result || Array.isArray(result)) return;
+
+	await importMod(result);
+}
+
+export function ImportModDropdown() {
+	return (
+		<CommandDropdown
+			label="Import Mod"
+			icon={<IconPackageImport />}
+		>
+			<CommandButton
+				leftSection={<IconFileZip />}
+				onClick={() => importFromDialog(false)}
+			>
+				Import from zip file
+			</CommandButton>
+			<CommandButton
+				leftSection={<IconFolder />}
+				onClick={() => importFromDialog(true)}
+			>
+				Import from folder
+			</CommandButton>
+		</CommandDropdown>
+	);
+}
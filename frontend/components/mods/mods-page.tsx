@@ -1,4 +1,4 @@
-import { Button, Group, Stack, Table, Text } from "@mantine/core";
+import { Alert, Button, Group, Stack, Table, Text } from "@mantine/core";
 import { useMemo, useState } from "react";
 import { TableContainer } from "@components/table/table-container";
 import { RefreshButton } from "@components/refresh-button";
@@ -12,6 +12,7 @@ import { ModModal } from "./mod-modal";
 import { useUnifiedMods } from "@hooks/use-unified-mods";
 import { ModVersionBadge } from "./mod-version-badge";
 import { ItemName } from "@components/item-name";
+import { ImportModDropdown } from "./import-mod-dropdown";
 
 export function ModsPage() {
 	const [selectedModId, setSelectedId] = useState<string>();
@@ -24,6 +25,16 @@ export function ModsPage() {
 		return result;
 	}, [selectedModId, mods]);
 
+	// If any database came from the offline cache, show the age of the oldest one.
+	const databaseCachedAt = useMemo(() => {
+		const cachedTimes = Object.values(mods)
+			.map((mod) => mod.remote?.databaseCachedAt)
+			.filter((cachedAt) => cachedAt !== undefined && cachedAt !== null)
+			.map(Number);
+
+		return cachedTimes.length > 0 ? Math.min(...cachedTimes) : undefined;
+	}, [mods]);
+
 	return (
 		<Stack>
 			{selectedMod ? (
@@ -39,8 +50,15 @@ export function ModsPage() {
 				>
 					Open Mods Folder
 				</Button>
+				<ImportModDropdown />
 				<RefreshButton />
 			</Group>
+			{databaseCachedAt !== undefined && (
+				<Alert color="orange">
+					Failed to reach the mod database. Showing mods from a copy saved on{" "}
+					{new Date(databaseCachedAt * 1000).toLocaleString()}.
+				</Alert>
+			)}
 			<TableContainer>
 				<Table highlightOnHover>
 					<Table.Thead>
@@ -53,6 +71,12 @@ export function ModsPage() {
 							>
 								Loader
 							</Table.Th>
+							<Table.Th
+								ta="center"
+								w={100}
+							>
+								Source
+							</Table.Th>
 							<Table.Th
 								w={100}
 								ta="center"
@@ -93,6 +117,7 @@ export function ModsPage() {
 									/>
 								</Table.Td>
 								<Table.Td>{mod.common.loaderId}</Table.Td>
+								<Table.Td>{mod.remote?.sourceId}</Table.Td>
 								<Table.Td>
 									<EngineBadge value={mod.common.engine} />
 								</Table.Td>
@@ -0,0 +1,57 @@
+import { JsonInput, Stack, Text } from "@mantine/core";
+import { useCallback, useEffect, useState } from "react";
+import { IconDeviceFloppy } from "@tabler/icons-react";
+import { CommandButton } from "@components/command-button";
+import { showAppNotification } from "@components/app-notifications";
+import { useUpdateData } from "@hooks/use-update-data";
+import { getDatabaseSources, setDatabaseSources } from "@api/bindings";
+
+export function DatabaseSourcesEditor() {
+	const [sourcesJson, setSourcesJson] = useState("");
+	const updateData = useUpdateData();
+
+	useEffect(() => {
+		getDatabaseSources()
+			.then((sources) => setSourcesJson(JSON.stringify(sources, null, 2)))
+			.catch((error) =>
+				showAppNotification(
+					`Failed to read database sources: ${error}`,
+					"error",
+				),
+			);
+	}, []);
+
+	const saveSources = useCallback(
+		() => setDatabaseSources(JSON.parse(sourcesJson)),
+		[sourcesJson],
+	);
+
+	return (
+		<Stack>
+			<Text size="sm">
+				Extra mod databases, as https:// or file:// urls. Each mod loader
+				database is read from [url]/[loader id].json. When the same mod is in
+				multiple databases, the one listed first wins. Source ids can only have
+				lowercase letters, numbers, - and _. Sources with publicKeys need
+				minisign signatures for their databases and downloads.
+			</Text>
+			<JsonInput
+				label="Database sources"
+				value={sourcesJson}
+				onChange={setSourcesJson}
+				validationError="Invalid JSON"
+				formatOnBlur
+				autosize
+				minRows={6}
+			/>
+			<CommandButton
+				onClick={saveSources}
+				onSuccess={updateData}
+				leftSection={<IconDeviceFloppy />}
+				justify="center"
+			>
+				Save database sources
+			</CommandButton>
+		</Stack>
+	);
+}
@@ -3,6 +3,7 @@ import { resetLocalStorage } from "../../util/local-storage";
 import { IconFolderCode, IconRotateDot } from "@tabler/icons-react";
 import { CommandButton } from "@components/command-button";
 import { openLogsFolder } from "@api/bindings";
+import { DatabaseSourcesEditor } from "./database-sources-editor";
 
 export function SettingsPage() {
 	return (
@@ -26,6 +27,7 @@ export function SettingsPage() {
 						Reset settings to defaults
 					</Button>
 				</Tooltip>
+				<DatabaseSourcesEditor />
 			</Stack>
 		</Container>
 	);
@@ -8,6 +8,7 @@ import {
 	getOwnedGames,
 	getLocalMods,
 	getRemoteMods,
+	getRunningRunnables,
 } from "@api/bindings";
 import { dataSubscription } from "./use-data-subscription";
 import { useUpdateData } from "./use-update-data";
@@ -40,6 +41,9 @@ export const [ownedGamesAtom, useOwnedGamesSubscription] = dataSubscription(
 	{},
 );
 
+export const [runningRunnablesAtom, useRunningRunnablesSubscription] =
+	dataSubscription("SyncRunningRunnables", getRunningRunnables, []);
+
 export const loadingAtom = atom<boolean>(false);
 
 export function useData() {
@@ -48,6 +52,7 @@ export function useData() {
 	useLocalModsSubscription();
 	useRemoteModsSubscription();
 	useOwnedGamesSubscription();
+	useRunningRunnablesSubscription();
 
 	const updateData = useUpdateData();
 
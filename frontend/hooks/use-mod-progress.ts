@@ -0,0 +1,32 @@
+import { ArchiveEntry, ModProgress } from "@api/bindings";
+import { useCallback, useState } from "react";
+import { useAppEvent } from "./use-app-event";
+
+export function useModProgress(modId: string) {
+	const [progress, setProgress] = useState<ModProgress>();
+	// Everything the current action is about to write, reported before extracting each archive.
+	const [entries, setEntries] = useState<ArchiveEntry[]>([]);
+
+	useAppEvent<ModProgress>("ModProgress", (payload) => {
+		if (payload.modId !== modId) return;
+
+		if (payload.entries.length > 0) {
+			setEntries((previousEntries) => [...previousEntries, ...payload.entries]);
+		}
+
+		setProgress(payload.phase === "Finished" ? undefined : payload);
+	});
+
+	const clearProgress = useCallback(() => {
+		setProgress(undefined);
+		setEntries([]);
+	}, []);
+
+	return [progress, clearProgress, entries] as const;
+}
+
+export function getProgressPercent(progress: ModProgress) {
+	if (!progress.total) return 100;
+
+	return (Number(progress.current) / Number(progress.total)) * 100;
+}